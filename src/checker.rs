@@ -0,0 +1,347 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::{
+    environment::Environment,
+    lexer::CaseMode,
+    syntax::{Printed, SExpression, Span, Term, Value},
+    termination,
+};
+
+/// A kind of suspicious code found without running it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Lint {
+    UnboundVariable,
+    /// A function applied to the wrong number of arguments.
+    ArityMismatch,
+    /// A `cond` clause after a clause whose predicate is `'T`.
+    UnreachableClause,
+    /// `eq` applied to a quoted pair, which is always an error.
+    EqOnPair,
+    /// A parameter with the same name as a variable bound around it.
+    ShadowedParameter,
+    UnusedParameter,
+    /// A `label` function that is not proven to terminate, see `termination::check`.
+    UnprovenTermination,
+}
+
+impl Lint {
+    pub const ALL: [Lint; 7] = [
+        Lint::UnboundVariable,
+        Lint::ArityMismatch,
+        Lint::UnreachableClause,
+        Lint::EqOnPair,
+        Lint::ShadowedParameter,
+        Lint::UnusedParameter,
+        Lint::UnprovenTermination,
+    ];
+
+    /// The code that names the lint, e.g. to suppress it.
+    pub fn code(self) -> &'static str {
+        match self {
+            Lint::UnboundVariable => "L001",
+            Lint::ArityMismatch => "L002",
+            Lint::UnreachableClause => "L003",
+            Lint::EqOnPair => "L004",
+            Lint::ShadowedParameter => "L005",
+            Lint::UnusedParameter => "L006",
+            Lint::UnprovenTermination => "L007",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Lint> {
+        Lint::ALL.into_iter().find(|lint| lint.code() == code)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Warning {
+    pub lint: Lint,
+    pub message: String,
+    /// Where the suspicious code is written, if it was parsed.
+    pub span: Option<Span>,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "warning[{}]: {}", self.lint.code(), self.message)
+    }
+}
+
+/// Finds suspicious code in terms without evaluating them.
+pub struct Checker {
+    /// The lints that are not reported.
+    pub allowed: HashSet<Lint>,
+    /// The case mode that the messages write symbols to read back in.
+    pub case_mode: CaseMode,
+    /// The variables bound before the terms are evaluated, with the number of parameters of
+    /// the functions among them.
+    globals: HashMap<String, Option<usize>>,
+}
+
+impl Checker {
+    /// Creates a checker for terms evaluated in `env`.
+    pub fn new(env: &Environment) -> Self {
+        Checker {
+            allowed: HashSet::new(),
+            case_mode: CaseMode::Preserve,
+            globals: env
+                .iter()
+                .map(|(name, value)| {
+                    let arity = match value {
+                        Value::Closure(closure) => Some(closure.params.len()),
+                        Value::Builtin(builtin) => Some(builtin.arity),
+                        _ => None,
+                    };
+                    (name.clone(), arity)
+                })
+                .collect(),
+        }
+    }
+
+    pub fn check(&self, term: &Term) -> Vec<Warning> {
+        let mut walker = Walker {
+            checker: self,
+            scope: vec![],
+            span: None,
+            warnings: vec![],
+        };
+        walker.walk(term);
+        for verdict in termination::check(term) {
+            if !verdict.proven {
+                walker.warn(
+                    Lint::UnprovenTermination,
+                    verdict.span,
+                    format!("cannot prove that {} terminates", verdict.name),
+                );
+            }
+        }
+        // NOTE: Warnings about a term are found before those about its subterms.
+        walker
+            .warnings
+            .sort_by_key(|warning| warning.span.map(|span| span.start));
+        walker.warnings
+    }
+}
+
+/// A variable bound by the term being checked.
+struct Binding {
+    name: String,
+    arity: Option<usize>,
+    used: bool,
+}
+
+struct Walker<'a> {
+    checker: &'a Checker,
+    /// The variables bound around the current term, innermost last.
+    scope: Vec<Binding>,
+    /// Where the current term is written, if it was parsed.
+    span: Option<Span>,
+    warnings: Vec<Warning>,
+}
+
+/// The number of parameters of a function written as `term`, if it is one.
+fn arity(term: &Term) -> Option<usize> {
+    match term.node() {
+        Term::Lambda(params, _) => Some(params.len()),
+        Term::Label(_, term) => arity(term),
+        _ => None,
+    }
+}
+
+impl Walker<'_> {
+    fn warn(&mut self, lint: Lint, span: Option<Span>, message: String) {
+        if !self.checker.allowed.contains(&lint) {
+            self.warnings.push(Warning {
+                lint,
+                message,
+                span,
+            });
+        }
+    }
+
+    fn walk(&mut self, term: &Term) {
+        match term {
+            Term::Spanned(span, term) => {
+                let outer = self.span.replace(*span);
+                self.walk(term);
+                self.span = outer;
+            }
+            Term::Cond(clauses) => {
+                let catch_all = clauses.iter().position(|(term, _)| {
+                    *term.node() == Term::Quote(SExpression::Symbol("T".to_string()))
+                });
+                if let Some((term, _)) = catch_all.and_then(|index| clauses.get(index + 1)) {
+                    let span = term.span().or(self.span);
+                    self.warn(
+                        Lint::UnreachableClause,
+                        span,
+                        "this clause is never reached after a clause whose predicate is 'T"
+                            .to_string(),
+                    );
+                }
+                self.walk_all(term.children());
+            }
+            Term::Primitive(primitive, terms) if primitive.name == "eq" => {
+                for term in terms {
+                    if let Term::Quote(sexp @ SExpression::Pair(_, _)) = term.node() {
+                        self.warn(
+                            Lint::EqOnPair,
+                            self.span,
+                            format!(
+                                "eq of a quoted pair {} is always an error",
+                                Printed(self.checker.case_mode, sexp)
+                            ),
+                        );
+                    }
+                }
+                self.walk_all(term.children());
+            }
+            Term::Variable(name) => {
+                if let Some(binding) = self.lookup(name) {
+                    binding.used = true;
+                } else if !self.checker.globals.contains_key(name) {
+                    self.warn(
+                        Lint::UnboundVariable,
+                        self.span,
+                        format!("unbound variable {}", name),
+                    );
+                }
+            }
+            Term::Lambda(params, body) => self.walk_lambda(params, body),
+            Term::Apply(callee, args) => {
+                let (name, expected) = match callee.node() {
+                    Term::Variable(name) => (name.as_str(), self.arity_of(name)),
+                    Term::Label(name, _) => (name.as_str(), arity(callee)),
+                    callee => ("the lambda", arity(callee)),
+                };
+                let splices = args
+                    .iter()
+                    .any(|arg| matches!(arg.node(), Term::UnquoteSplicing(_)));
+                if let Some(expected) = expected.filter(|_| !splices) {
+                    if expected != args.len() {
+                        self.warn(
+                            Lint::ArityMismatch,
+                            self.span,
+                            format!(
+                                "{} expects {} argument(s), but is applied to {}",
+                                name,
+                                expected,
+                                args.len()
+                            ),
+                        );
+                    }
+                }
+                self.walk_all(term.children());
+            }
+            Term::Label(name, term) => {
+                self.bind_all(vec![(name.clone(), arity(term))]);
+                self.walk(term);
+                self.unbind(1);
+            }
+            Term::Labels(bindings, _) => {
+                self.bind_all(
+                    bindings
+                        .iter()
+                        .map(|(name, term)| (name.clone(), arity(term)))
+                        .collect(),
+                );
+                self.walk_all(term.children());
+                self.unbind(bindings.len());
+            }
+            Term::Quasiquote(term) => self.walk_unquoted(term),
+            Term::Defmacro(_, params, body) => {
+                // NOTE: The body of a macro sees nothing but its parameters.
+                let scope = std::mem::take(&mut self.scope);
+                self.walk_lambda(params, body);
+                self.scope = scope;
+            }
+            // NOTE: The arguments of a macro are not necessarily evaluated.
+            Term::MacroCall(_, _) => (),
+            term => self.walk_all(term.children()),
+        }
+    }
+
+    fn walk_all(&mut self, terms: Vec<&Term>) {
+        for term in terms {
+            self.walk(term);
+        }
+    }
+
+    fn walk_lambda(&mut self, params: &[String], body: &Term) {
+        for (index, param) in params.iter().enumerate() {
+            let shadows = params[..index].contains(param)
+                || self.scope.iter().any(|binding| binding.name == *param);
+            if shadows {
+                self.warn(
+                    Lint::ShadowedParameter,
+                    self.span,
+                    format!("parameter {} shadows another variable", param),
+                );
+            }
+        }
+
+        let span = self.span;
+        let len = self.scope.len();
+        self.scope.extend(params.iter().map(|param| Binding {
+            name: param.clone(),
+            arity: None,
+            used: false,
+        }));
+        self.walk(body);
+        for binding in self.scope.split_off(len) {
+            if !binding.used {
+                self.warn(
+                    Lint::UnusedParameter,
+                    span,
+                    format!("parameter {} is never used", binding.name),
+                );
+            }
+        }
+    }
+
+    /// Walks the parts of a quasiquote template that are evaluated when it is filled in.
+    fn walk_unquoted(&mut self, term: &Term) {
+        match term {
+            Term::Spanned(span, term) => {
+                let outer = self.span.replace(*span);
+                self.walk_unquoted(term);
+                self.span = outer;
+            }
+            Term::Unquote(term) | Term::UnquoteSplicing(term) => self.walk(term),
+            term => {
+                for term in term.children() {
+                    self.walk_unquoted(term);
+                }
+            }
+        }
+    }
+
+    /// Binds the functions of `label` or `labels`, which need not be called.
+    fn bind_all(&mut self, bindings: Vec<(String, Option<usize>)>) {
+        self.scope
+            .extend(bindings.into_iter().map(|(name, arity)| Binding {
+                name,
+                arity,
+                used: true,
+            }));
+    }
+
+    fn unbind(&mut self, count: usize) {
+        self.scope.truncate(self.scope.len() - count);
+    }
+
+    fn lookup(&mut self, name: &String) -> Option<&mut Binding> {
+        self.scope
+            .iter_mut()
+            .rev()
+            .find(|binding| binding.name == *name)
+    }
+
+    fn arity_of(&mut self, name: &String) -> Option<usize> {
+        match self.lookup(name) {
+            Some(binding) => binding.arity,
+            None => self.checker.globals.get(name).copied().flatten(),
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::HashSet;
+use std::io::{BufRead, Write};
+
+use crate::{
+    environment::Environment,
+    interpreter::{Context, Frame},
+    syntax::{Printed, Term},
+};
+
+/// Receives every term right before it is evaluated.
+pub trait Debugger {
+    /// Called before `term` is evaluated in `env`.
+    /// `ctx` tells how deep the evaluation is and which applications are in progress.
+    fn before_eval(&mut self, term: &Term, env: &Environment, ctx: &Context);
+}
+
+/// When a `Stepper` pauses next.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Mode {
+    /// Before the next term.
+    Step,
+    /// Before the next term that is not a subterm of the one at the given depth.
+    Next(usize),
+    /// Before the next term after the application with the given number of frames returns.
+    Finish(usize),
+    /// At breakpoints only.
+    Continue,
+}
+
+/// An interactive debugger reading commands from `R` and writing to `W`.
+///
+/// It pauses before the first term, and then as its commands say.
+/// Symbols are written to read back in the case mode of the context.
+pub struct Stepper<R: BufRead, W: Write> {
+    input: R,
+    output: W,
+    mode: Mode,
+    /// The names of the functions to pause in when they are applied.
+    pub breakpoints: HashSet<String>,
+    frames: usize,
+}
+
+const HELP: &str = "\
+step (s)           evaluate the next term
+next (n)           evaluate the current term without pausing in it
+finish (f)         return from the current function
+continue (c)       run until a breakpoint
+break (b) NAME     pause when the function NAME is applied
+delete (d) NAME    remove the breakpoint on NAME
+env (e)            print the bindings of the current environment
+stack (bt)         print the applications in progress";
+
+impl<R: BufRead, W: Write> Stepper<R, W> {
+    pub fn new(input: R, output: W) -> Self {
+        Stepper {
+            input,
+            output,
+            mode: Mode::Step,
+            breakpoints: HashSet::new(),
+            frames: 0,
+        }
+    }
+
+    fn should_pause(&self, ctx: &Context) -> bool {
+        let frames = ctx.frames();
+        let entered = frames.len() > self.frames
+            && frames
+                .last()
+                .is_some_and(|frame| self.breakpoints.contains(frame.name()));
+
+        entered
+            || match self.mode {
+                Mode::Step => true,
+                Mode::Next(depth) => ctx.depth() <= depth,
+                Mode::Finish(len) => frames.len() < len,
+                Mode::Continue => false,
+            }
+    }
+
+    /// Reads commands until one of them resumes the evaluation.
+    fn pause(&mut self, term: &Term, env: &Environment, ctx: &Context) -> std::io::Result<()> {
+        let case_mode = ctx.case_mode;
+        writeln!(self.output, "at {}", Printed(case_mode, term))?;
+        loop {
+            write!(self.output, "debug> ")?;
+            self.output.flush()?;
+
+            let mut line = String::new();
+            if self.input.read_line(&mut line)? == 0 {
+                // NOTE: Without any more commands, run to the end.
+                self.mode = Mode::Continue;
+                self.breakpoints.clear();
+                return Ok(());
+            }
+
+            let mut words = line.split_whitespace();
+            match (words.next(), words.next()) {
+                (None | Some("step" | "s"), None) => self.mode = Mode::Step,
+                (Some("next" | "n"), None) => self.mode = Mode::Next(ctx.depth()),
+                (Some("finish" | "f"), None) => self.mode = Mode::Finish(ctx.frames().len()),
+                (Some("continue" | "c"), None) => self.mode = Mode::Continue,
+                (Some("break" | "b"), Some(name)) => {
+                    self.breakpoints.insert(name.to_string());
+                    continue;
+                }
+                (Some("delete" | "d"), Some(name)) => {
+                    self.breakpoints.remove(name);
+                    continue;
+                }
+                (Some("env" | "e"), None) => {
+                    let mut bindings = env.iter().collect::<Vec<_>>();
+                    bindings.sort_by_key(|(name, _)| *name);
+                    for (name, value) in bindings {
+                        let value = ctx.export(value);
+                        writeln!(self.output, "{} = {}", name, Printed(case_mode, &value))?;
+                    }
+                    continue;
+                }
+                (Some("stack" | "bt"), None) => {
+                    for (index, frame) in ctx.frames().iter().rev().enumerate() {
+                        let frame = Frame {
+                            args: frame.args.iter().map(|arg| ctx.export(arg)).collect(),
+                            ..frame.clone()
+                        };
+                        let printed = Printed(case_mode, &frame);
+                        match frame.span {
+                            Some(span) => {
+                                writeln!(self.output, "#{} {} at {}", index, printed, span)?
+                            }
+                            None => writeln!(self.output, "#{} {}", index, printed)?,
+                        }
+                    }
+                    continue;
+                }
+                _ => {
+                    writeln!(self.output, "{}", HELP)?;
+                    continue;
+                }
+            }
+            return Ok(());
+        }
+    }
+}
+
+impl<R: BufRead, W: Write> Debugger for Stepper<R, W> {
+    fn before_eval(&mut self, term: &Term, env: &Environment, ctx: &Context) {
+        if self.should_pause(ctx) {
+            // NOTE: If the debugger cannot talk to the user, let the evaluation run to the end.
+            if self.pause(term, env, ctx).is_err() {
+                self.mode = Mode::Continue;
+                self.breakpoints.clear();
+            }
+        }
+        self.frames = ctx.frames().len();
+    }
+}
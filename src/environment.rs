@@ -13,6 +13,14 @@ impl Environment {
         self.0.get(name)
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Value)> {
+        self.0.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&String, &mut Value)> {
+        self.0.iter_mut()
+    }
+
     pub fn extend(&mut self, name: String, value: Value) -> Option<Value> {
         self.0.insert(name, value)
     }
@@ -0,0 +1,329 @@
+use std::fmt;
+use std::io;
+
+use crate::{
+    interpreter::Frame,
+    lexer::CaseMode,
+    primitive::Primitive,
+    syntax::{Print, Printed, Span, Value},
+    types::Type,
+};
+
+/// An error raised by the evaluation, with the applications it went through.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EvalError {
+    pub kind: ErrorKind,
+    /// The applications in progress when the error was raised, outermost first.
+    pub backtrace: Vec<Frame>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    UnboundVariable(String),
+    /// `car` or `cdr` applied to something other than a pair.
+    NotAPair(&'static str, Value),
+    /// A step of a `c[ad]+r` accessor such as `caddr` applied to something other than a pair.
+    /// The steps are counted from 1, innermost first.
+    NotAPairInAccessor {
+        accessor: &'static Primitive,
+        step: usize,
+        value: Value,
+    },
+    /// An arithmetic primitive applied to something other than integers.
+    NotAnInteger(&'static str, Value),
+    /// A string primitive applied to something other than a string.
+    NotAString(&'static str, Value),
+    /// A primitive on symbols applied to something other than a symbol.
+    NotASymbol(&'static str, Value),
+    /// `implode` applied to a list with something other than a single-character symbol.
+    NotACharacter(Value),
+    /// `quotient` or `remainder` by zero.
+    DivisionByZero(&'static str),
+    /// `eq` applied to something other than atoms.
+    NotAnAtom(Value),
+    NotAFunction(Value),
+    ArityMismatch {
+        expected: usize,
+        found: usize,
+    },
+    /// Every predicate of a `cond` (or `if`) is false.
+    NoTrueClause,
+    /// A predicate is neither `T` nor `F` in strict boolean mode.
+    NonBooleanPredicate {
+        form: &'static str,
+        index: usize,
+        value: Value,
+    },
+    /// `label` or `labels` binds something other than a `lambda`.
+    NotALambda(String),
+    /// A value spliced into a template cannot be written as a term.
+    NotQuotable(Value),
+    /// A value spliced with `,@` is not a list.
+    NotAList(Value),
+    UnquoteOutsideQuasiquote,
+    MacroNotExpanded(String),
+    /// An error raised by a builtin, described by its own message.
+    Custom(String),
+}
+
+impl Print for ErrorKind {
+    fn fmt_with(&self, case_mode: CaseMode, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let printed = |value| Printed(case_mode, value);
+        match self {
+            ErrorKind::UnboundVariable(name) => write!(f, "unbound variable {}", name),
+            ErrorKind::NotAPair(name, value) => write!(f, "{} of an atom {}", name, printed(value)),
+            ErrorKind::NotAPairInAccessor {
+                accessor,
+                step,
+                value,
+            } => {
+                let path = accessor.name.as_bytes();
+                let name = match path[path.len() - 1 - step] {
+                    b'a' => "car",
+                    _ => "cdr",
+                };
+                write!(
+                    f,
+                    "{} of an atom {} at step {} of {}",
+                    name,
+                    printed(value),
+                    step,
+                    accessor.name
+                )
+            }
+            ErrorKind::NotAnInteger(name, value) => {
+                write!(f, "{} of a non-integer {}", name, printed(value))
+            }
+            ErrorKind::NotAString(name, value) => {
+                write!(f, "{} of a non-string {}", name, printed(value))
+            }
+            ErrorKind::NotASymbol(name, value) => {
+                write!(f, "{} of a non-symbol {}", name, printed(value))
+            }
+            ErrorKind::NotACharacter(value) => {
+                write!(
+                    f,
+                    "implode of a list with a non-character {}",
+                    printed(value)
+                )
+            }
+            ErrorKind::DivisionByZero(name) => write!(f, "{} by zero", name),
+            ErrorKind::NotAnAtom(value) => write!(f, "eq of a non-atom {}", printed(value)),
+            ErrorKind::NotAFunction(value) => write!(f, "{} is not a function", printed(value)),
+            ErrorKind::ArityMismatch { expected, found } => {
+                write!(f, "expected {} argument(s), but found {}", expected, found)
+            }
+            ErrorKind::NoTrueClause => write!(f, "no clause has a true predicate"),
+            ErrorKind::NonBooleanPredicate { form, index, value } => write!(
+                f,
+                "the predicate #{} of {} is {}, which is neither T nor F",
+                index + 1,
+                form,
+                printed(value)
+            ),
+            ErrorKind::NotALambda(name) => write!(f, "{} is not bound to a lambda", name),
+            ErrorKind::NotQuotable(value) => write!(f, "{} cannot be quoted", printed(value)),
+            ErrorKind::NotAList(value) => write!(f, "{} is not a list", printed(value)),
+            ErrorKind::UnquoteOutsideQuasiquote => write!(f, "unquote outside a quasiquote"),
+            ErrorKind::MacroNotExpanded(name) => write!(f, "macro {} is not expanded", name),
+            ErrorKind::Custom(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with(CaseMode::Preserve, f)
+    }
+}
+
+impl From<ErrorKind> for EvalError {
+    fn from(kind: ErrorKind) -> Self {
+        EvalError {
+            kind,
+            backtrace: vec![],
+        }
+    }
+}
+
+impl Print for EvalError {
+    fn fmt_with(&self, case_mode: CaseMode, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.kind.fmt_with(case_mode, f)
+    }
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)
+    }
+}
+
+impl std::error::Error for EvalError {}
+
+/// A syntax error, with where it was found.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// A token that cannot continue the term, as written.
+    UnexpectedToken(String),
+    UnexpectedEnd,
+    /// A failure to read the input, with its message.
+    Io(String),
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseErrorKind::UnexpectedToken(word) => write!(f, "unexpected `{}`", word),
+            ParseErrorKind::UnexpectedEnd => write!(f, "unexpected end of input"),
+            ParseErrorKind::Io(message) => write!(f, "cannot read the input: {}", message),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// An error raised while expanding a call of a macro, with the name of the macro.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MacroError {
+    pub name: String,
+    pub kind: MacroErrorKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MacroErrorKind {
+    /// A call of a name that no `defmacro` has defined.
+    UnknownMacro,
+    ArityMismatch {
+        expected: usize,
+        found: usize,
+    },
+    /// An error raised by the body of the macro, boxed as it is much larger than the others.
+    Eval(Box<EvalError>),
+    /// A value given by the body of the macro that is not a term.
+    NotATerm(Value),
+}
+
+impl Print for MacroError {
+    fn fmt_with(&self, case_mode: CaseMode, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            MacroErrorKind::UnknownMacro => write!(f, "unknown macro {}", self.name),
+            MacroErrorKind::ArityMismatch { expected, found } => write!(
+                f,
+                "macro {} expected {} argument(s), but found {}",
+                self.name, expected, found
+            ),
+            MacroErrorKind::Eval(err) => {
+                write!(f, "macro {}: {}", self.name, Printed(case_mode, &**err))
+            }
+            MacroErrorKind::NotATerm(value) => write!(
+                f,
+                "macro {} expanded to {}, which is not a term",
+                self.name,
+                Printed(case_mode, value)
+            ),
+        }
+    }
+}
+
+impl fmt::Display for MacroError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with(CaseMode::Preserve, f)
+    }
+}
+
+impl std::error::Error for MacroError {}
+
+/// An error raised by `Interpreter`, at any stage from reading the source to evaluating it.
+#[derive(Debug)]
+pub enum InterpreterError {
+    Io(io::Error),
+    /// All the syntax errors of the source, which is not evaluated at all.
+    Parse(Vec<ParseError>),
+    MacroExpansion(MacroError),
+    Eval(EvalError),
+}
+
+impl fmt::Display for InterpreterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InterpreterError::Io(err) => write!(f, "{}", err),
+            InterpreterError::Parse(errors) => {
+                let errors = errors
+                    .iter()
+                    .map(|err| format!("{} at {}", err, err.span))
+                    .collect::<Vec<_>>();
+                write!(f, "{}", errors.join(", "))
+            }
+            InterpreterError::MacroExpansion(err) => write!(f, "{}", err),
+            InterpreterError::Eval(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for InterpreterError {}
+
+impl From<io::Error> for InterpreterError {
+    fn from(err: io::Error) -> Self {
+        InterpreterError::Io(err)
+    }
+}
+
+impl From<MacroError> for InterpreterError {
+    fn from(err: MacroError) -> Self {
+        InterpreterError::MacroExpansion(err)
+    }
+}
+
+impl From<EvalError> for InterpreterError {
+    fn from(err: EvalError) -> Self {
+        InterpreterError::Eval(err)
+    }
+}
+
+/// A term without a type, with where it was found.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeError {
+    pub kind: TypeErrorKind,
+    pub span: Option<Span>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TypeErrorKind {
+    Mismatch(Type, Type),
+    /// A type that would have to contain itself.
+    Infinite(Type),
+    UnboundVariable(String),
+    /// A term that the checker does not type, e.g. a macro that is not expanded.
+    Unsupported(&'static str),
+}
+
+impl fmt::Display for TypeErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TypeErrorKind::Mismatch(ty1, ty2) => write!(f, "{} does not match {}", ty1, ty2),
+            TypeErrorKind::Infinite(ty) => write!(f, "the type {} would contain itself", ty),
+            TypeErrorKind::UnboundVariable(name) => write!(f, "unbound variable {}", name),
+            TypeErrorKind::Unsupported(what) => write!(f, "{} cannot be typed", what),
+        }
+    }
+}
+
+impl fmt::Display for TypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)
+    }
+}
+
+impl std::error::Error for TypeError {}
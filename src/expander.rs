@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+
+use crate::{
+    environment::Environment,
+    error::{MacroError, MacroErrorKind},
+    interpreter::Context,
+    syntax::{SExpression, Term, Value},
+};
+
+struct Macro {
+    params: Vec<String>,
+    body: Term,
+}
+
+/// Rewrites macro calls into the terms they stand for, before evaluation.
+#[derive(Default)]
+pub struct Expander {
+    macros: HashMap<String, Macro>,
+}
+
+impl Expander {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn expand(&mut self, term: Term) -> Result<Term, MacroError> {
+        match term {
+            Term::Defmacro(name, params, body) => {
+                let body = self.expand(*body)?;
+                self.macros.insert(name.clone(), Macro { params, body });
+                Ok(Term::Quote(SExpression::Symbol(name)))
+            }
+            Term::MacroCall(name, args) => {
+                let term = self.expand_once(&name, args)?;
+                self.expand(term)
+            }
+            // NOTE: A template is expanded once it has been filled in at a call site.
+            Term::Quasiquote(term) => Ok(Term::Quasiquote(Box::new(self.expand_unquoted(*term)?))),
+            term => term.try_map_children(&mut |term| self.expand(term)),
+        }
+    }
+
+    /// Expands a single macro call without expanding the resulting term further.
+    pub fn expand_once(&self, name: &String, args: Vec<Box<Term>>) -> Result<Term, MacroError> {
+        let error = |kind| MacroError {
+            name: name.clone(),
+            kind,
+        };
+        let Macro { params, body } = self
+            .macros
+            .get(name)
+            .ok_or_else(|| error(MacroErrorKind::UnknownMacro))?;
+        if params.len() != args.len() {
+            return Err(error(MacroErrorKind::ArityMismatch {
+                expected: params.len(),
+                found: args.len(),
+            }));
+        }
+
+        let mut env = Environment::new();
+        for (param, arg) in params.iter().zip(args) {
+            env.extend(param.clone(), Value::Code(arg));
+        }
+        let value = body
+            .clone()
+            .eval_with(&mut env, &mut Context::new())
+            .map_err(|err| error(MacroErrorKind::Eval(Box::new(err))))?;
+        value
+            .clone()
+            .into_term()
+            .ok_or_else(|| error(MacroErrorKind::NotATerm(value)))
+    }
+
+    fn expand_unquoted(&mut self, term: Term) -> Result<Term, MacroError> {
+        match term {
+            Term::Unquote(term) => Ok(Term::Unquote(Box::new(self.expand(*term)?))),
+            Term::UnquoteSplicing(term) => Ok(Term::UnquoteSplicing(Box::new(self.expand(*term)?))),
+            term => term.try_map_children(&mut |term| self.expand_unquoted(term)),
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use std::{collections::HashSet, mem};
+
+use crate::{environment::Environment, syntax::Value};
+
+/// Counters reported by the heap.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub live_cells: usize,
+    pub allocations: usize,
+    pub collections: usize,
+    pub cells_reclaimed: usize,
+    pub bytes_reclaimed: usize,
+}
+
+/// An arena of cons cells managed by a mark-and-sweep collector.
+///
+/// Cells are referred to by `Value::Cell`. A collection has to be given every value that is
+/// still in use as a root: between top-level terms that is the environment, and during an
+/// evaluation `Context` adds its frames and intermediate values.
+#[derive(Clone, Debug, Default)]
+pub struct Heap {
+    cells: Vec<Option<(Value, Value)>>,
+    free: Vec<usize>,
+    stats: Stats,
+    /// The number of cells that survived the last collection.
+    survivors: usize,
+}
+
+impl Heap {
+    /// The number of allocations before the first collection is worth it.
+    const MIN_THRESHOLD: usize = 1024;
+
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn alloc(&mut self, car: Value, cdr: Value) -> Value {
+        self.stats.live_cells += 1;
+        self.stats.allocations += 1;
+
+        match self.free.pop() {
+            Some(index) => {
+                self.cells[index] = Some((car, cdr));
+                Value::Cell(index)
+            }
+            None => {
+                self.cells.push(Some((car, cdr)));
+                Value::Cell(self.cells.len() - 1)
+            }
+        }
+    }
+
+    pub fn get(&self, index: usize) -> Option<&(Value, Value)> {
+        self.cells.get(index)?.as_ref()
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    /// Whether the heap has grown enough since the last collection to collect it again.
+    pub fn needs_collection(&self) -> bool {
+        self.stats.live_cells >= Self::MIN_THRESHOLD.max(2 * self.survivors)
+    }
+
+    /// Frees every cell unreachable from `env`, returning the number of cells freed.
+    pub fn collect(&mut self, env: &Environment) -> usize {
+        self.collect_from(env.iter().map(|(_, value)| value))
+    }
+
+    /// Frees every cell unreachable from `roots`, returning the number of cells freed.
+    pub fn collect_from<'a>(&mut self, roots: impl IntoIterator<Item = &'a Value>) -> usize {
+        let marks = self.mark(roots);
+
+        let mut reclaimed = 0;
+        for (index, cell) in self.cells.iter_mut().enumerate() {
+            if cell.is_some() && !marks[index] {
+                *cell = None;
+                self.free.push(index);
+                reclaimed += 1;
+            }
+        }
+
+        self.stats.live_cells -= reclaimed;
+        self.survivors = self.stats.live_cells;
+        self.stats.collections += 1;
+        self.stats.cells_reclaimed += reclaimed;
+        self.stats.bytes_reclaimed += reclaimed * mem::size_of::<Option<(Value, Value)>>();
+        reclaimed
+    }
+
+    /// Copies a value so that it no longer refers to the heap.
+    pub fn export(&self, value: &Value) -> Value {
+        match value {
+            Value::Cell(index) => match self.get(*index) {
+                Some((car, cdr)) => {
+                    Value::Pair(Box::new(self.export(car)), Box::new(self.export(cdr)))
+                }
+                None => value.clone(),
+            },
+            Value::Pair(car, cdr) => {
+                Value::Pair(Box::new(self.export(car)), Box::new(self.export(cdr)))
+            }
+            value => value.clone(),
+        }
+    }
+
+    fn mark<'a>(&self, roots: impl IntoIterator<Item = &'a Value>) -> Vec<bool> {
+        let mut marks = vec![false; self.cells.len()];
+        let mut visited_envs = HashSet::new();
+        let mut stack = roots.into_iter().cloned().collect::<Vec<_>>();
+
+        while let Some(value) = stack.pop() {
+            match value {
+                Value::Cell(index) => {
+                    if let (Some(false), Some((car, cdr))) = (marks.get(index), self.get(index)) {
+                        marks[index] = true;
+                        stack.push(car.clone());
+                        stack.push(cdr.clone());
+                    }
+                }
+                Value::Pair(car, cdr) => {
+                    stack.push(*car);
+                    stack.push(*cdr);
+                }
+                Value::Closure(closure) => {
+                    if let Some(env) = closure.env.upgrade() {
+                        if visited_envs.insert(closure.env.as_ptr()) {
+                            stack.extend(env.borrow().iter().map(|(_, value)| value.clone()));
+                        }
+                    }
+                }
+                Value::Symbol(_)
+                | Value::Integer(_)
+                | Value::String(_)
+                | Value::Builtin(_)
+                | Value::Code(_) => (),
+            }
+        }
+
+        marks
+    }
+}
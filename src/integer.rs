@@ -0,0 +1,253 @@
+use std::{cmp::Ordering, fmt, ops, str::FromStr};
+
+/// The base of the digits of an `Integer`, so that each of them prints as nine decimal digits.
+const BASE: u64 = 1_000_000_000;
+
+/// An integer of any size.
+// NOTE: The digits are stored from the least significant, without leading zeros,
+// and zero is never negative, so that equal integers have equal representations.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub struct Integer {
+    negative: bool,
+    digits: Vec<u32>,
+}
+
+impl Integer {
+    pub fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// The quotient truncated toward zero and the remainder, which has the sign of `self`,
+    /// or `None` if `other` is zero.
+    pub fn div_rem(&self, other: &Integer) -> Option<(Integer, Integer)> {
+        if other.is_zero() {
+            return None;
+        }
+        let (quotient, remainder) = div_rem_digits(&self.digits, &other.digits);
+        Some((
+            Integer::new(self.negative != other.negative, quotient),
+            Integer::new(self.negative, remainder),
+        ))
+    }
+
+    fn new(negative: bool, mut digits: Vec<u32>) -> Self {
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+        Integer {
+            negative: negative && !digits.is_empty(),
+            digits,
+        }
+    }
+}
+
+impl From<i64> for Integer {
+    fn from(n: i64) -> Self {
+        let mut magnitude = n.unsigned_abs();
+        let mut digits = vec![];
+        while magnitude > 0 {
+            digits.push((magnitude % BASE) as u32);
+            magnitude /= BASE;
+        }
+        Integer::new(n < 0, digits)
+    }
+}
+
+/// The error of parsing something other than decimal digits, with an optional sign, as an `Integer`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseIntegerError;
+
+impl FromStr for Integer {
+    type Err = ParseIntegerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, s) = match s.strip_prefix('-') {
+            Some(s) => (true, s),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        if s.is_empty() || !s.bytes().all(|c| c.is_ascii_digit()) {
+            return Err(ParseIntegerError);
+        }
+
+        let digits = s
+            .as_bytes()
+            .rchunks(9)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .fold(0, |digit, c| digit * 10 + u32::from(c - b'0'))
+            })
+            .collect();
+        Ok(Integer::new(negative, digits))
+    }
+}
+
+impl fmt::Display for Integer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some((last, rest)) = self.digits.split_last() else {
+            return write!(f, "0");
+        };
+        if self.negative {
+            write!(f, "-")?;
+        }
+        write!(f, "{}", last)?;
+        for digit in rest.iter().rev() {
+            write!(f, "{:09}", digit)?;
+        }
+        Ok(())
+    }
+}
+
+impl Ord for Integer {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_digits(&self.digits, &other.digits),
+            (true, true) => cmp_digits(&other.digits, &self.digits),
+        }
+    }
+}
+
+impl PartialOrd for Integer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl ops::Neg for &Integer {
+    type Output = Integer;
+
+    fn neg(self) -> Integer {
+        Integer::new(!self.negative, self.digits.clone())
+    }
+}
+
+impl ops::Add for &Integer {
+    type Output = Integer;
+
+    fn add(self, other: &Integer) -> Integer {
+        if self.negative == other.negative {
+            return Integer::new(self.negative, add_digits(&self.digits, &other.digits));
+        }
+        // NOTE: The sum of integers of different signs is the difference of their magnitudes.
+        match cmp_digits(&self.digits, &other.digits) {
+            Ordering::Less => Integer::new(other.negative, sub_digits(&other.digits, &self.digits)),
+            _ => Integer::new(self.negative, sub_digits(&self.digits, &other.digits)),
+        }
+    }
+}
+
+impl ops::Sub for &Integer {
+    type Output = Integer;
+
+    fn sub(self, other: &Integer) -> Integer {
+        self + &-other
+    }
+}
+
+impl ops::Mul for &Integer {
+    type Output = Integer;
+
+    fn mul(self, other: &Integer) -> Integer {
+        Integer::new(
+            self.negative != other.negative,
+            mul_digits(&self.digits, &other.digits),
+        )
+    }
+}
+
+fn cmp_digits(digits1: &[u32], digits2: &[u32]) -> Ordering {
+    digits1
+        .len()
+        .cmp(&digits2.len())
+        .then_with(|| digits1.iter().rev().cmp(digits2.iter().rev()))
+}
+
+fn add_digits(digits1: &[u32], digits2: &[u32]) -> Vec<u32> {
+    let mut digits = vec![];
+    let mut carry = 0;
+    for index in 0..digits1.len().max(digits2.len()) {
+        let sum = u64::from(*digits1.get(index).unwrap_or(&0))
+            + u64::from(*digits2.get(index).unwrap_or(&0))
+            + carry;
+        digits.push((sum % BASE) as u32);
+        carry = sum / BASE;
+    }
+    if carry > 0 {
+        digits.push(carry as u32);
+    }
+    digits
+}
+
+/// Subtracts `digits2` from `digits1`, which must not be smaller.
+fn sub_digits(digits1: &[u32], digits2: &[u32]) -> Vec<u32> {
+    let mut digits = vec![];
+    let mut borrow = 0;
+    for (index, digit) in digits1.iter().enumerate() {
+        let subtrahend = i64::from(*digits2.get(index).unwrap_or(&0)) + borrow;
+        let mut difference = i64::from(*digit) - subtrahend;
+        borrow = 0;
+        if difference < 0 {
+            difference += BASE as i64;
+            borrow = 1;
+        }
+        digits.push(difference as u32);
+    }
+    while digits.last() == Some(&0) {
+        digits.pop();
+    }
+    digits
+}
+
+fn mul_digits(digits1: &[u32], digits2: &[u32]) -> Vec<u32> {
+    if digits1.is_empty() || digits2.is_empty() {
+        return vec![];
+    }
+    let mut digits = vec![0u64; digits1.len() + digits2.len()];
+    for (index1, digit1) in digits1.iter().enumerate() {
+        let mut carry = 0;
+        for (index2, digit2) in digits2.iter().enumerate() {
+            let product = digits[index1 + index2] + u64::from(*digit1) * u64::from(*digit2) + carry;
+            digits[index1 + index2] = product % BASE;
+            carry = product / BASE;
+        }
+        digits[index1 + digits2.len()] += carry;
+    }
+    let mut digits = digits
+        .into_iter()
+        .map(|digit| digit as u32)
+        .collect::<Vec<_>>();
+    while digits.last() == Some(&0) {
+        digits.pop();
+    }
+    digits
+}
+
+/// Divides magnitudes by long division, finding each digit of the quotient by bisection.
+fn div_rem_digits(digits1: &[u32], digits2: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    let mut quotient = vec![0; digits1.len()];
+    let mut remainder: Vec<u32> = vec![];
+    for (index, digit) in digits1.iter().enumerate().rev() {
+        remainder.insert(0, *digit);
+        while remainder.last() == Some(&0) {
+            remainder.pop();
+        }
+
+        let (mut low, mut high) = (0, BASE as u32 - 1);
+        while low < high {
+            let middle = low + (high - low).div_ceil(2);
+            match cmp_digits(&mul_digits(digits2, &[middle]), &remainder) {
+                Ordering::Greater => high = middle - 1,
+                _ => low = middle,
+            }
+        }
+        quotient[index] = low;
+        remainder = sub_digits(&remainder, &mul_digits(digits2, &[low]));
+    }
+    (quotient, remainder)
+}
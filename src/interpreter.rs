@@ -1,15 +1,427 @@
 use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
 use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use crate::{
+    debugger::Debugger,
     environment::Environment,
-    syntax::{SExpression, Term, Value},
+    error::{ErrorKind, EvalError, InterpreterError},
+    expander::Expander,
+    heap::Heap,
+    lexer::{CaseMode, Lexer},
+    parser::Parser,
+    prelude,
+    syntax::{Builtin, Captured, Closure, Print, Printed, SExpression, Span, Term, Value},
+    tracer::Tracer,
 };
 
+/// How predicates of `cond`, `if`, `and`, `or` and `not` are read as truth values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum BooleanMode {
+    /// `T` is true and anything else is false, as in the paper.
+    #[default]
+    Standard,
+    /// `T` is true, `F` is false and anything else is an error.
+    Strict,
+    /// `NIL` is false and anything else is true, as in LISP 1.5.
+    /// Predicates give `NIL` instead of `F`.
+    Lisp15,
+}
+
+/// An application of a closure in progress.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    /// The name of the closure, or of the variable it was applied through, if any.
+    pub name: Option<String>,
+    pub args: Vec<Value>,
+    /// Where the application is written, if it was parsed.
+    pub span: Option<Span>,
+}
+
+impl Frame {
+    pub fn name(&self) -> &str {
+        self.name.as_deref().unwrap_or("<lambda>")
+    }
+}
+
+impl Print for Frame {
+    fn fmt_with(&self, case_mode: CaseMode, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}", self.name())?;
+        for arg in &self.args {
+            write!(f, " {}", Printed(case_mode, arg))?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with(CaseMode::Preserve, f)
+    }
+}
+
+/// The state shared by a whole evaluation.
+#[derive(Default)]
+pub struct Context {
+    pub boolean_mode: BooleanMode,
+    /// The case mode that the debugger writes symbols to read back in.
+    pub case_mode: CaseMode,
+    /// Where `cons` allocates pairs, if pairs are managed by a collector.
+    /// It is collected at applications, keeping what the evaluation in progress still uses;
+    /// the values of earlier evaluations are only kept if they are bound in its environment.
+    pub heap: Option<Heap>,
+    /// Receives the applications of traced functions.
+    pub tracer: Option<Box<dyn Tracer>>,
+    /// The names of the traced functions.
+    pub traced: HashSet<String>,
+    /// Whether every function is traced.
+    pub trace_all: bool,
+    /// Receives every term before it is evaluated.
+    pub debugger: Option<Box<dyn Debugger>>,
+    frames: Vec<Frame>,
+    /// The values besides the frames that the evaluation in progress still uses, if there is a heap:
+    /// the bindings of its top-level environment, the closures being applied and the values of
+    /// arguments evaluated before the others.
+    roots: Vec<Value>,
+    traced_depth: usize,
+    depth: usize,
+    span: Option<Span>,
+}
+
+impl Context {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// The applications in progress, outermost first.
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    /// Where the innermost term being evaluated is written, if it was parsed.
+    pub fn span(&self) -> Option<Span> {
+        self.span
+    }
+
+    /// The number of terms being evaluated, including their subterms.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    fn is_traced(&self, frame: &Frame) -> bool {
+        self.tracer.is_some()
+            && (self.trace_all
+                || frame
+                    .name
+                    .as_ref()
+                    .is_some_and(|name| self.traced.contains(name)))
+    }
+
+    /// Keeps `value` through collections until the roots are truncated again, if there is a heap.
+    fn root(&mut self, value: &Value) {
+        if self.heap.is_some() {
+            self.roots.push(value.clone());
+        }
+    }
+
+    /// Collects the heap if it has grown enough, from the roots and frames of the evaluation.
+    fn collect_if_needed(&mut self) {
+        if let Some(heap) = &mut self.heap {
+            if heap.needs_collection() {
+                let args = self.frames.iter().flat_map(|frame| &frame.args);
+                heap.collect_from(self.roots.iter().chain(args));
+            }
+        }
+    }
+
+    /// Collects the heap if it has grown enough, keeping only what `env` holds.
+    ///
+    /// Nothing else is in use between top-level terms, unless the caller keeps their values.
+    pub fn collect_garbage(&mut self, env: &Environment) {
+        if let Some(heap) = &mut self.heap {
+            if heap.needs_collection() {
+                heap.collect(env);
+            }
+        }
+    }
+
+    /// Runs `body` on the arguments of `frame` as the application it describes.
+    fn call<F>(&mut self, frame: Frame, body: F) -> Result<Value, EvalError>
+    where
+        F: FnOnce(Vec<Value>, &mut Context) -> Result<Value, EvalError>,
+    {
+        let args = frame.args.clone();
+        let traced = self.is_traced(&frame);
+        if traced {
+            let args = frame
+                .args
+                .iter()
+                .map(|arg| self.export(arg))
+                .collect::<Vec<_>>();
+            if let Some(tracer) = &mut self.tracer {
+                tracer.enter(self.traced_depth, frame.name(), &args);
+            }
+            self.traced_depth += 1;
+        }
+        self.frames.push(frame);
+        self.collect_if_needed();
+
+        let mut result = body(args, self);
+        if let Err(err) = &mut result {
+            // NOTE: The innermost application records the whole stack, while it is still there.
+            if err.backtrace.is_empty() {
+                err.backtrace = self
+                    .frames
+                    .iter()
+                    .map(|frame| Frame {
+                        args: frame.args.iter().map(|arg| self.export(arg)).collect(),
+                        ..frame.clone()
+                    })
+                    .collect();
+            }
+        }
+
+        let frame = self.frames.pop().unwrap();
+        if traced {
+            self.traced_depth -= 1;
+            let result = result
+                .as_ref()
+                .map(|value| self.export(value))
+                .map_err(Clone::clone);
+            if let Some(tracer) = &mut self.tracer {
+                tracer.exit(self.traced_depth, frame.name(), &result);
+            }
+        }
+        result
+    }
+
+    /// Copies a value so that it no longer refers to the heap, e.g. to print it.
+    pub fn export(&self, value: &Value) -> Value {
+        match &self.heap {
+            Some(heap) => heap.export(value),
+            None => value.clone(),
+        }
+    }
+
+    pub(crate) fn cons(&mut self, car: Value, cdr: Value) -> Value {
+        match &mut self.heap {
+            Some(heap) => heap.alloc(car, cdr),
+            None => Value::Pair(Box::new(car), Box::new(cdr)),
+        }
+    }
+
+    /// Splits a pair into its components, or gives the value back if it is not a pair.
+    pub(crate) fn uncons(&self, value: Value) -> Result<(Value, Value), Value> {
+        match (value, &self.heap) {
+            (Value::Pair(car, cdr), _) => Ok((*car, *cdr)),
+            (Value::Cell(index), Some(heap)) => match heap.get(index) {
+                Some((car, cdr)) => Ok((car.clone(), cdr.clone())),
+                None => Err(Value::Cell(index)),
+            },
+            (value, _) => Err(value),
+        }
+    }
+
+    pub(crate) fn truth(&self, b: bool) -> Value {
+        match (b, self.boolean_mode) {
+            (true, _) => symbol("T"),
+            (false, BooleanMode::Lisp15) => symbol("NIL"),
+            (false, _) => symbol("F"),
+        }
+    }
+
+    /// Reads the `index`-th predicate of `form` as a truth value.
+    fn holds(&self, value: Value, form: &'static str, index: usize) -> Result<bool, EvalError> {
+        match self.boolean_mode {
+            BooleanMode::Standard => Ok(value == symbol("T")),
+            BooleanMode::Strict => {
+                if value == symbol("T") {
+                    Ok(true)
+                } else if value == symbol("F") {
+                    Ok(false)
+                } else {
+                    Err(ErrorKind::NonBooleanPredicate {
+                        form,
+                        index,
+                        value: self.export(&value),
+                    }
+                    .into())
+                }
+            }
+            BooleanMode::Lisp15 => Ok(value != symbol("NIL")),
+        }
+    }
+}
+
+/// Reads and evaluates programs in an environment that persists between them.
+pub struct Interpreter {
+    pub env: Environment,
+    pub ctx: Context,
+    expander: Expander,
+    case_mode: CaseMode,
+}
+
+impl Default for Interpreter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Interpreter {
+    /// Creates an interpreter whose environment has the functions of the prelude.
+    pub fn new() -> Self {
+        let mut env = Environment::new();
+        prelude::load(&mut env);
+        Self::with_env(env)
+    }
+
+    pub fn with_env(env: Environment) -> Self {
+        Interpreter {
+            env,
+            ctx: Context::new(),
+            expander: Expander::new(),
+            case_mode: CaseMode::Preserve,
+        }
+    }
+
+    /// Creates an interpreter whose environment has the functions of the prelude,
+    /// which reads identifiers in `case_mode`.
+    ///
+    /// The functions are bound under their names as read in `case_mode`, e.g. `CAR` in uppercase.
+    pub fn with_case_mode(case_mode: CaseMode) -> Self {
+        let mut env = Environment::new();
+        prelude::load_in(&mut env, case_mode);
+        let mut interpreter = Self::with_env(env);
+        interpreter.case_mode = case_mode;
+        interpreter.ctx.case_mode = case_mode;
+        interpreter
+    }
+
+    pub fn case_mode(&self) -> CaseMode {
+        self.case_mode
+    }
+
+    /// Writes `value` so that it reads back in the case mode of the interpreter.
+    pub fn print(&self, value: &Value) -> String {
+        Printed(self.case_mode, value).to_string()
+    }
+
+    /// Evaluates every term of `source` in order, and gives the value of the last one,
+    /// or `NIL` if there is none.
+    pub fn eval_str(&mut self, source: &str) -> Result<Value, InterpreterError> {
+        self.eval_reader(source.as_bytes())
+    }
+
+    pub fn eval_file(&mut self, path: impl AsRef<Path>) -> Result<Value, InterpreterError> {
+        let file = File::open(path)?;
+        self.eval_reader(BufReader::new(file))
+    }
+
+    /// Evaluates every term read from `reader` as `eval_str` does, after parsing all of them.
+    ///
+    /// The terms are all kept in memory until then on purpose, so that a syntax error anywhere
+    /// leaves the environment as it was. Iterate a `Parser` to evaluate each term as it is read.
+    pub fn eval_reader(&mut self, reader: impl BufRead) -> Result<Value, InterpreterError> {
+        let lexer = Lexer::from_reader(reader, self.case_mode);
+        let (terms, errors) = Parser::new(lexer).parse_all();
+        if !errors.is_empty() {
+            return Err(InterpreterError::Parse(errors));
+        }
+
+        let mut value = symbol("NIL");
+        for term in terms {
+            // NOTE: The value of the term before is no longer in use.
+            self.ctx.collect_garbage(&self.env);
+            let term = self.expander.expand(term)?;
+            value = term.eval_with(&mut self.env, &mut self.ctx)?;
+        }
+        Ok(self.ctx.export(&value))
+    }
+
+    /// Binds `value` to `name` as read in the case mode of the interpreter,
+    /// replacing what it was bound to.
+    pub fn define(&mut self, name: &str, value: Value) {
+        self.env.extend(self.case_mode.fold(name), value);
+    }
+
+    /// The value bound to `name` as read in the case mode of the interpreter.
+    pub fn get(&self, name: &str) -> Option<Value> {
+        let value = self.env.lookup(&self.case_mode.fold(name))?;
+        Some(self.ctx.export(value))
+    }
+
+    /// Binds `name` to a function written in Rust, which is applied with `apply` like a lambda.
+    pub fn register_builtin<F>(&mut self, name: &str, arity: usize, function: F)
+    where
+        F: Fn(&[Value]) -> Result<Value, ErrorKind> + 'static,
+    {
+        let builtin = Builtin {
+            name: self.case_mode.fold(name),
+            arity,
+            function: Rc::new(function),
+        };
+        self.define(name, Value::Builtin(builtin));
+    }
+}
+
+fn symbol(name: &str) -> Value {
+    Value::Symbol(name.to_string())
+}
+
+/// Makes a closure hold its environment strongly, before the value leaves that environment.
+fn strengthen(value: &mut Value) {
+    if let Value::Closure(Closure {
+        env: captured @ Captured::Weak(_),
+        ..
+    }) = value
+    {
+        *captured = Captured::Strong(
+            captured
+                .upgrade()
+                .expect("The environment of a closure should outlive its evaluation"),
+        );
+    }
+}
+
+/// Binds functions made by `label` or `labels` in the environment they share.
+fn bind_recursively(
+    bindings: Vec<(String, Box<Term>)>,
+    env: &Environment,
+) -> Result<(Rc<RefCell<Environment>>, Vec<Value>), EvalError> {
+    let new_env = Rc::new(RefCell::new(env.clone()));
+    let mut values = vec![];
+    for (name, term) in bindings {
+        match term.into_node() {
+            Term::Lambda(params, body) => {
+                let closure = Closure {
+                    name: Some(name.clone()),
+                    params,
+                    body,
+                    env: Captured::Weak(Rc::downgrade(&new_env)),
+                };
+                values.push(Value::Closure(Closure {
+                    env: Captured::Strong(Rc::clone(&new_env)),
+                    ..closure.clone()
+                }));
+                new_env.borrow_mut().extend(name, Value::Closure(closure));
+            }
+            _ => return Err(ErrorKind::NotALambda(name).into()),
+        }
+    }
+    Ok((new_env, values))
+}
+
 impl SExpression {
     pub fn eval(self) -> Value {
         match self {
             SExpression::Symbol(name) => Value::Symbol(name),
+            SExpression::Integer(n) => Value::Integer(n),
+            SExpression::String(s) => Value::String(s),
             SExpression::Pair(sexp1, sexp2) => {
                 Value::Pair(Box::new((*sexp1).eval()), Box::new((*sexp2).eval()))
             }
@@ -17,83 +429,364 @@ impl SExpression {
     }
 }
 
+impl Value {
+    /// Turns a value back into a term, e.g. to splice it into a quasiquote template.
+    pub fn into_term(self) -> Option<Term> {
+        fn to_sexpression(value: Value) -> Option<SExpression> {
+            match value {
+                Value::Symbol(name) => Some(SExpression::Symbol(name)),
+                Value::Integer(n) => Some(SExpression::Integer(n)),
+                Value::String(s) => Some(SExpression::String(s)),
+                Value::Pair(value1, value2) => Some(SExpression::Pair(
+                    Box::new(to_sexpression(*value1)?),
+                    Box::new(to_sexpression(*value2)?),
+                )),
+                _ => None,
+            }
+        }
+
+        match self {
+            Value::Code(term) => Some(*term),
+            value => Some(Term::Quote(to_sexpression(value)?)),
+        }
+    }
+}
+
 impl Term {
     pub fn eval(self, env: &mut Environment) -> Option<Value> {
-        let t = Value::Symbol("T".to_string());
-        let f = Value::Symbol("F".to_string());
+        self.eval_with(env, &mut Context::new()).ok()
+    }
+
+    pub fn eval_with(self, env: &mut Environment, ctx: &mut Context) -> Result<Value, EvalError> {
+        if let Term::Spanned(span, term) = self {
+            let outer = ctx.span.replace(span);
+            let result = (*term).eval_with(env, ctx);
+            ctx.span = outer;
+            return result;
+        }
+
+        // NOTE: The debugger is taken out while it runs, so that it can look at the context.
+        if let Some(mut debugger) = ctx.debugger.take() {
+            debugger.before_eval(&self, env, ctx);
+            ctx.debugger = Some(debugger);
+        }
 
+        // NOTE: Roots left by an evaluation that failed are dropped once the next one starts.
+        if ctx.depth == 0 {
+            ctx.roots.clear();
+            if ctx.heap.is_some() {
+                ctx.roots.extend(env.iter().map(|(_, value)| value.clone()));
+            }
+        }
+
+        ctx.depth += 1;
+        let result = self.eval_step(env, ctx);
+        ctx.depth -= 1;
+        result
+    }
+
+    /// Evaluates the term itself, leaving its subterms to `eval_with`.
+    fn eval_step(self, env: &mut Environment, ctx: &mut Context) -> Result<Value, EvalError> {
         match self {
             /* Conditional Expressions */
             Term::Cond(pairs) => {
-                for (term1, term2) in pairs {
-                    if (*term1).eval(env)? == t {
-                        return Some((*term2).eval(env)?);
+                for (index, (term1, term2)) in pairs.into_iter().enumerate() {
+                    let value = (*term1).eval_with(env, ctx)?;
+                    if ctx.holds(value, "cond", index)? {
+                        return (*term2).eval_with(env, ctx);
+                    }
+                }
+
+                Err(ErrorKind::NoTrueClause.into())
+            }
+            Term::And(terms) => {
+                for (index, term) in terms.into_iter().enumerate() {
+                    let value = (*term).eval_with(env, ctx)?;
+                    if !ctx.holds(value, "and", index)? {
+                        return Ok(ctx.truth(false));
+                    }
+                }
+
+                Ok(ctx.truth(true))
+            }
+            Term::Or(terms) => {
+                for (index, term) in terms.into_iter().enumerate() {
+                    let value = (*term).eval_with(env, ctx)?;
+                    if ctx.holds(value, "or", index)? {
+                        return Ok(ctx.truth(true));
                     }
                 }
 
-                None
+                Ok(ctx.truth(false))
+            }
+            Term::Not(term) => {
+                let value = (*term).eval_with(env, ctx)?;
+                Ok(ctx.truth(!ctx.holds(value, "not", 0)?))
+            }
+            Term::If(term1, term2, term3) => {
+                let value = (*term1).eval_with(env, ctx)?;
+                if ctx.holds(value, "if", 0)? {
+                    (*term2).eval_with(env, ctx)
+                } else {
+                    (*term3.ok_or(ErrorKind::NoTrueClause)?).eval_with(env, ctx)
+                }
             }
             /* The Elementary S-functions and Predicates */
-            Term::Atom(term) => match (*term).eval(env)? {
-                Value::Symbol(_) => Some(t),
-                _ => Some(f),
-            },
-            Term::Eq(term1, term2) => match ((*term1).eval(env)?, (*term2).eval(env)?) {
-                (Value::Symbol(str1), Value::Symbol(str2)) => {
-                    if str1 == str2 {
-                        Some(t)
-                    } else {
-                        Some(f)
+            Term::Primitive(primitive, terms) => {
+                if primitive.arity != terms.len() {
+                    return Err(ErrorKind::ArityMismatch {
+                        expected: primitive.arity,
+                        found: terms.len(),
                     }
+                    .into());
                 }
-                _ => None,
-            },
-            Term::Car(term) => match (*term).eval(env)? {
-                Value::Pair(fst, _) => Some(*fst),
-                _ => None,
-            },
-            Term::Cdr(term) => match (*term).eval(env)? {
-                Value::Pair(_, snd) => Some(*snd),
-                _ => None,
-            },
-            Term::Cons(term1, term2) => Some(Value::Pair(
-                Box::new((*term1).eval(env)?),
-                Box::new((*term2).eval(env)?),
-            )),
+
+                let roots = ctx.roots.len();
+                let mut args = vec![];
+                for term in terms {
+                    let arg = (*term).eval_with(env, ctx)?;
+                    ctx.root(&arg);
+                    args.push(arg);
+                }
+                ctx.roots.truncate(roots);
+                Ok((primitive.function)(ctx, args)?)
+            }
             /* Functions and Forms */
-            Term::Variable(name) => env.lookup(&name).map(|var| var.clone()),
-            Term::Lambda(params, body) => Some(Value::Closure(
-                params,
-                body,
-                Rc::new(RefCell::new(env.clone())),
-            )),
-            Term::Apply(term, terms) => match (*term).eval(env)? {
-                Value::Closure(params, body, env2) => {
-                    if params.len() == terms.len() {
-                        let env2 = Rc::new(RefCell::new(env2.borrow().clone()));
-                        let mut new_env = env2.borrow_mut();
-                        for (param, term) in params.iter().zip(terms.iter()) {
-                            new_env.extend(param.clone(), (*term.clone()).eval(env)?);
+            Term::Variable(name) => {
+                let mut value = env
+                    .lookup(&name)
+                    .cloned()
+                    .ok_or(ErrorKind::UnboundVariable(name))?;
+                strengthen(&mut value);
+                Ok(value)
+            }
+            Term::Lambda(params, body) => {
+                let mut new_env = env.clone();
+                for (_, value) in new_env.iter_mut() {
+                    strengthen(value);
+                }
+                Ok(Value::Closure(Closure {
+                    name: None,
+                    params,
+                    body,
+                    env: Captured::Strong(Rc::new(RefCell::new(new_env))),
+                }))
+            }
+            Term::Apply(term, terms) => {
+                let span = ctx.span;
+                let callee = match term.node() {
+                    Term::Variable(name) => Some(name.clone()),
+                    _ => None,
+                };
+
+                let roots = ctx.roots.len();
+                let value = (*term).eval_with(env, ctx)?;
+                ctx.root(&value);
+                let result = match value {
+                    Value::Closure(closure) => {
+                        if closure.params.len() != terms.len() {
+                            return Err(ErrorKind::ArityMismatch {
+                                expected: closure.params.len(),
+                                found: terms.len(),
+                            }
+                            .into());
+                        }
+
+                        let mut args = vec![];
+                        for term in terms {
+                            let arg = (*term).eval_with(env, ctx)?;
+                            ctx.root(&arg);
+                            args.push(arg);
                         }
-                        (*body).eval(&mut new_env)
-                    } else {
-                        None
+                        // NOTE: The arguments are kept by the frame from now on, and the closure by its root.
+                        ctx.roots.truncate(roots + 1);
+                        let name = closure.name.clone().or(callee);
+                        let frame = Frame { name, args, span };
+                        ctx.call(frame, |args, ctx| {
+                            // NOTE: `closure` keeps its environment alive until the body has been evaluated.
+                            let mut new_env = closure
+                                .env
+                                .upgrade()
+                                .expect("The environment of an applied closure should be alive")
+                                .borrow()
+                                .clone();
+                            for (param, arg) in closure.params.iter().zip(args) {
+                                new_env.extend(param.clone(), arg);
+                            }
+                            (*closure.body).eval_with(&mut new_env, ctx)
+                        })
                     }
-                }
-                _ => None,
-            },
+                    Value::Builtin(builtin) => {
+                        if builtin.arity != terms.len() {
+                            return Err(ErrorKind::ArityMismatch {
+                                expected: builtin.arity,
+                                found: terms.len(),
+                            }
+                            .into());
+                        }
+
+                        let mut args = vec![];
+                        for term in terms {
+                            let arg = (*term).eval_with(env, ctx)?;
+                            ctx.root(&arg);
+                            args.push(arg);
+                        }
+                        ctx.roots.truncate(roots);
+                        let name = Some(builtin.name.clone());
+                        let frame = Frame { name, args, span };
+                        ctx.call(frame, |args, ctx| {
+                            // NOTE: A builtin never sees the pairs on the heap.
+                            let args = args.iter().map(|arg| ctx.export(arg)).collect::<Vec<_>>();
+                            Ok((builtin.function)(&args)?)
+                        })
+                    }
+                    value => Err(ErrorKind::NotAFunction(ctx.export(&value)).into()),
+                };
+                ctx.roots.truncate(roots);
+                result
+            }
             /* Expressions for Recursive Functions */
-            Term::Label(name, term) => match *term {
-                Term::Lambda(params, body) => {
-                    let new_env = Rc::new(RefCell::new(env.clone()));
-                    let value = Value::Closure(params, body, Rc::clone(&new_env));
-                    new_env.borrow_mut().extend(name, value.clone());
-                    Some(value)
-                }
-                _ => None,
-            },
+            Term::Label(name, term) => {
+                let (_, mut values) = bind_recursively(vec![(name, term)], env)?;
+                Ok(values.remove(0))
+            }
+            Term::Labels(bindings, term) => {
+                // NOTE: All functions share one environment, so that they can call each other.
+                let (new_env, _) = bind_recursively(bindings, env)?;
+                let mut body_env = new_env.borrow().clone();
+                (*term).eval_with(&mut body_env, ctx)
+            }
             /* Functions and Forms */
-            Term::Quote(sexp) => Some(sexp.eval()),
+            Term::Quote(sexp) => Ok(sexp.eval()),
+            /* Debugging */
+            Term::Trace(name) => {
+                ctx.traced.insert(name.clone());
+                Ok(Value::Symbol(name))
+            }
+            Term::Untrace(name) => {
+                ctx.traced.remove(&name);
+                Ok(Value::Symbol(name))
+            }
+            /* Macros */
+            Term::Quasiquote(term) => Ok(Value::Code(Box::new((*term).fill(
+                env,
+                ctx,
+                &HashMap::new(),
+            )?))),
+            // NOTE: The rest only make sense inside a template or before expansion.
+            Term::Unquote(_) | Term::UnquoteSplicing(_) => {
+                Err(ErrorKind::UnquoteOutsideQuasiquote.into())
+            }
+            Term::Defmacro(name, _, _) | Term::MacroCall(name, _) => {
+                Err(ErrorKind::MacroNotExpanded(name).into())
+            }
+            /* Source Locations */
+            Term::Spanned(_, term) => (*term).eval_with(env, ctx),
+        }
+    }
+
+    /// Fills the holes of a quasiquote template.
+    ///
+    /// Variables bound by the template itself are renamed to fresh names,
+    /// so that they never capture variables of the terms spliced into it.
+    fn fill(
+        self,
+        env: &mut Environment,
+        ctx: &mut Context,
+        renames: &HashMap<String, String>,
+    ) -> Result<Term, EvalError> {
+        fn fresh(name: &str, renames: &mut HashMap<String, String>) -> String {
+            static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+            let fresh = format!("{}%{}", name, COUNTER.fetch_add(1, Ordering::Relaxed));
+            renames.insert(name.to_string(), fresh.clone());
+            fresh
+        }
+
+        fn quote(value: Value, ctx: &Context) -> Result<Term, EvalError> {
+            let value = ctx.export(&value);
+            value
+                .clone()
+                .into_term()
+                .ok_or(ErrorKind::NotQuotable(value).into())
+        }
+
+        // NOTE: Arguments are kept boxed, as they are in `Term::Apply`.
+        #[allow(clippy::vec_box)]
+        fn fill_all(
+            terms: Vec<Box<Term>>,
+            env: &mut Environment,
+            ctx: &mut Context,
+            renames: &HashMap<String, String>,
+        ) -> Result<Vec<Box<Term>>, EvalError> {
+            let mut filled = vec![];
+            for term in terms {
+                match *term {
+                    Term::UnquoteSplicing(term) => {
+                        let mut list = (*term).eval_with(env, ctx)?;
+                        loop {
+                            match ctx.uncons(list) {
+                                Ok((value1, value2)) => {
+                                    filled.push(Box::new(quote(value1, ctx)?));
+                                    list = value2;
+                                }
+                                Err(Value::Symbol(name)) if name == "NIL" => break,
+                                Err(value) => return Err(ErrorKind::NotAList(value).into()),
+                            }
+                        }
+                    }
+                    term => filled.push(Box::new(term.fill(env, ctx, renames)?)),
+                }
+            }
+            Ok(filled)
+        }
+
+        match self {
+            Term::Unquote(term) => quote((*term).eval_with(env, ctx)?, ctx),
+            Term::UnquoteSplicing(_) => Err(ErrorKind::UnquoteOutsideQuasiquote.into()),
+            Term::Variable(name) => Ok(Term::Variable(renames.get(&name).cloned().unwrap_or(name))),
+            Term::Lambda(params, body) => {
+                let mut renames = renames.clone();
+                let params = params
+                    .iter()
+                    .map(|param| fresh(param, &mut renames))
+                    .collect();
+                Ok(Term::Lambda(
+                    params,
+                    Box::new((*body).fill(env, ctx, &renames)?),
+                ))
+            }
+            Term::Label(name, term) => {
+                let mut renames = renames.clone();
+                let name = fresh(&name, &mut renames);
+                Ok(Term::Label(
+                    name,
+                    Box::new((*term).fill(env, ctx, &renames)?),
+                ))
+            }
+            Term::Labels(bindings, term) => {
+                let mut renames = renames.clone();
+                let names = bindings
+                    .iter()
+                    .map(|(name, _)| fresh(name, &mut renames))
+                    .collect::<Vec<_>>();
+                let mut filled = vec![];
+                for (name, (_, term)) in names.into_iter().zip(bindings) {
+                    filled.push((name, Box::new((*term).fill(env, ctx, &renames)?)));
+                }
+                Ok(Term::Labels(
+                    filled,
+                    Box::new((*term).fill(env, ctx, &renames)?),
+                ))
+            }
+            Term::Apply(term, terms) => Ok(Term::Apply(
+                Box::new((*term).fill(env, ctx, renames)?),
+                fill_all(terms, env, ctx, renames)?,
+            )),
+            Term::MacroCall(name, terms) => {
+                Ok(Term::MacroCall(name, fill_all(terms, env, ctx, renames)?))
+            }
+            term => term.try_map_children(&mut |term| term.fill(env, ctx, renames)),
         }
     }
 }
@@ -1,15 +1,106 @@
-use crate::token::Token;
+use crate::{integer::Integer, syntax::Span, token::Token};
 use regex::Regex;
+use std::{
+    collections::VecDeque,
+    io::{self, BufRead},
+};
 
-pub struct Lexer {
-    buf: Box<dyn Iterator<Item = String>>,
+/// How the letters of identifiers are read, so that e.g. `x` and `X` can be the same symbol.
+///
+/// The parts of an identifier between bars are read as they are written in any mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum CaseMode {
+    /// Letters are read as they are written, so `x` and `X` are different symbols.
+    #[default]
+    Preserve,
+    /// Letters are read in uppercase, as the atoms are written in the paper.
+    Upcase,
+    /// Letters are read in lowercase.
+    Downcase,
 }
 
-impl Lexer {
-    pub fn new(s: &String) -> Self {
-        let re = Regex::new(
+/// The most bytes that a word may take, so that e.g. an unterminated string does not read
+/// the rest of the input in search of its end.
+const MAX_WORD_LEN: usize = 1 << 20;
+
+/// The atoms that the interpreter gives and expects, which are named in uppercase.
+const ATOMS: [&str; 3] = ["T", "F", "NIL"];
+
+impl CaseMode {
+    /// The name that `name` is read as when written without bars.
+    ///
+    /// The atoms `T`, `F` and `NIL` are read from their names in the mode, e.g. from `nil`
+    /// when letters are read in lowercase, so that they are the atoms the interpreter expects.
+    pub fn fold(self, name: &str) -> String {
+        let folded = self.fold_letters(name);
+        match ATOMS
+            .into_iter()
+            .find(|atom| self.fold_letters(atom) == folded)
+        {
+            Some(atom) => atom.to_string(),
+            None => folded,
+        }
+    }
+
+    /// How the symbol `name` is written without bars, e.g. `nil` for `NIL` in lowercase.
+    pub fn unfold(self, name: &str) -> String {
+        if ATOMS.contains(&name) {
+            self.fold_letters(name)
+        } else {
+            name.to_string()
+        }
+    }
+
+    fn fold_letters(self, name: &str) -> String {
+        match self {
+            CaseMode::Preserve => name.to_string(),
+            CaseMode::Upcase => name.chars().flat_map(char::to_uppercase).collect(),
+            CaseMode::Downcase => name.chars().flat_map(char::to_lowercase).collect(),
+        }
+    }
+}
+
+pub struct Lexer<'a> {
+    reader: Box<dyn BufRead + 'a>,
+    /// The input read so far, of which the words from `consumed` on have not been lexed yet.
+    text: String,
+    consumed: usize,
+    /// Whether the reader has run out, or failed.
+    exhausted: bool,
+    /// The error the reader failed with, until it is taken.
+    error: Option<io::Error>,
+    /// The words lexed ahead of the last one, e.g. to look past spaces.
+    buf: VecDeque<(String, Span)>,
+    word_re: Regex,
+    integer_re: Regex,
+    ident_re: Regex,
+    space_re: Regex,
+    /// The last word read, or `None` once the input has run out.
+    last: Option<(String, Span)>,
+    /// The empty span where the next word to be lexed starts.
+    position: Span,
+    /// The number of parentheses read but not closed yet.
+    depth: isize,
+    case_mode: CaseMode,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(s: &'a str) -> Self {
+        Self::with_case_mode(s, CaseMode::Preserve)
+    }
+
+    pub fn with_case_mode(s: &'a str, case_mode: CaseMode) -> Self {
+        Self::from_reader(s.as_bytes(), case_mode)
+    }
+
+    /// Creates a lexer that reads its input from `reader` a line at a time, as words are asked for.
+    pub fn from_reader(reader: impl BufRead + 'a, case_mode: CaseMode) -> Self {
+        // NOTE: Keywords and integers are lexed as identifiers and told apart in `next_token`,
+        // so that e.g. `labels` is not split into `label` and `s`, nor `1+` into `1` and `+`.
+        let word_re = Regex::new(
             format!(
-                r"'|label|apply|lambda|cons|cdr|car|eq|atom|cond|,|\(|\)|{}|{}|.*",
+                r"^(?:'|`|,@|,|\(|\)|{}|{}|{})",
+                Self::string_regex(),
                 Self::ident_regex(),
                 Self::space_regex()
             )
@@ -18,39 +109,112 @@ impl Lexer {
         .unwrap();
 
         Lexer {
-            buf: Box::new(
-                re.find_iter(s.as_str())
-                    .map(|m| m.as_str().to_string())
-                    // NOTE: Create a vector to avoid an error about `re` lifetime
-                    .collect::<Vec<String>>()
-                    .into_iter(),
-            ),
+            reader: Box::new(reader),
+            text: String::new(),
+            consumed: 0,
+            exhausted: false,
+            error: None,
+            buf: VecDeque::new(),
+            word_re,
+            integer_re: Regex::new(&format!("^(?:{})$", Self::integer_regex())).unwrap(),
+            ident_re: Regex::new(&format!("^(?:{})$", Self::ident_regex())).unwrap(),
+            space_re: Regex::new(Self::space_regex()).unwrap(),
+            last: None,
+            position: Span {
+                start: 0,
+                end: 0,
+                line: 1,
+                column: 1,
+            },
+            depth: 0,
+            case_mode,
         }
     }
 
+    pub fn case_mode(&self) -> CaseMode {
+        self.case_mode
+    }
+
+    /// The error the reader failed with, if it has.
+    ///
+    /// The input is taken to end where the reader failed.
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+
+    /// The span of the last token, or the end of the input once it has run out.
+    pub fn span(&self) -> Span {
+        self.last.as_ref().map_or(self.position, |(_, span)| *span)
+    }
+
+    /// The last token as written, or `None` once the input has run out.
+    pub fn word(&self) -> Option<&str> {
+        self.last.as_ref().map(|(word, _)| word.as_str())
+    }
+
+    /// Whether only spaces are left.
+    pub fn at_end(&mut self) -> bool {
+        loop {
+            let Some((word, _)) = self.buf.front() else {
+                match self.lex() {
+                    Some(word) => self.buf.push_back(word),
+                    None => return true,
+                }
+                continue;
+            };
+            if !word.trim().is_empty() {
+                return false;
+            }
+            self.buf.pop_front();
+        }
+    }
+
+    /// Skips the rest of a malformed top-level form.
+    ///
+    /// The form ends where its parentheses are balanced,
+    /// or right before a parenthesis at the start of a line, which is taken to open the next form.
+    pub fn recover(&mut self) {
+        let opens_form = |(word, span): &(String, Span)| word == "(" && span.column == 1;
+
+        if self.last.as_ref().is_some_and(opens_form) {
+            self.buf.push_front(self.last.take().unwrap());
+        } else {
+            // NOTE: `at_end` drops the spaces, so that the next word is a token.
+            while self.depth > 0 && !self.at_end() && !opens_form(&self.buf[0]) {
+                self.next_token();
+            }
+        }
+        self.depth = 0;
+    }
+
     pub fn next_token(&mut self) -> Option<Token> {
-        let Some(s) = self.buf.next() else {
-            return None;
-        };
+        self.last = self.buf.pop_front().or_else(|| self.lex());
+        let s = self.last.as_ref()?.0.clone();
 
         match s.as_str() {
             "'" => Some(Token::Quote),
-            "label" => Some(Token::Label),
-            "apply" => Some(Token::Apply),
-            "lambda" => Some(Token::Lambda),
-            "cons" => Some(Token::Cons),
-            "cdr" => Some(Token::Cdr),
-            "car" => Some(Token::Car),
-            "eq" => Some(Token::Eq),
-            "atom" => Some(Token::Atom),
-            "cond" => Some(Token::Cond),
+            "`" => Some(Token::Backquote),
+            ",@" => Some(Token::CommaAt),
             "," => Some(Token::Comma),
-            ")" => Some(Token::RParen),
-            "(" => Some(Token::LParen),
+            ")" => {
+                self.depth -= 1;
+                Some(Token::RParen)
+            }
+            "(" => {
+                self.depth += 1;
+                Some(Token::LParen)
+            }
+            s if s.len() >= 2 && s.starts_with('"') && s.ends_with('"') => {
+                unescape(&s[1..s.len() - 1]).map(Token::String)
+            }
             s => {
-                if Regex::new(&Self::ident_regex()).unwrap().is_match(s) {
-                    Some(Token::Id(s.to_string()))
-                } else if Regex::new(&Self::space_regex()).unwrap().is_match(s) {
+                if let Some(token) = keyword(s, self.case_mode) {
+                    Some(token)
+                } else if self.integer_re.is_match(s) {
+                    s.parse().ok().map(Token::Integer)
+                } else if self.ident_re.is_match(s) {
+                    Some(Token::Id(unescape_symbol(s, self.case_mode)))
+                } else if self.space_re.is_match(s) {
                     self.next_token()
                 } else {
                     None
@@ -59,11 +223,175 @@ impl Lexer {
         }
     }
 
+    /// Lexes the next word of the input, reading as much more of it as the word needs,
+    /// or gives `None` once the input has run out.
+    ///
+    /// A word is anything up to the end of the line that is not a token, such as an unterminated string.
+    /// Past `MAX_WORD_LEN` bytes, the input is taken to end for the word being lexed.
+    fn lex(&mut self) -> Option<(String, Span)> {
+        // NOTE: A word that reaches the end of the text read so far may go on in the rest of the input,
+        // and so may text that is no token yet, such as a string closed on a later line.
+        let len = loop {
+            let rest = &self.text[self.consumed..];
+            let len = self.word_re.find(rest).map(|m| m.end());
+            match len {
+                Some(len) if len < rest.len() => break len,
+                _ if self.exhausted || rest.len() >= MAX_WORD_LEN => {
+                    break len.unwrap_or_else(|| rest.find('\n').unwrap_or(rest.len()))
+                }
+                _ => self.read_more(),
+            }
+        };
+        if len == 0 {
+            return None;
+        }
+
+        let word = self.text[self.consumed..self.consumed + len].to_string();
+        self.consumed += len;
+        let start = self.position;
+        for c in word.chars() {
+            if c == '\n' {
+                self.position.line += 1;
+                self.position.column = 1;
+            } else {
+                self.position.column += 1;
+            }
+        }
+        self.position.start += len;
+        self.position.end += len;
+        Some((
+            word,
+            Span {
+                end: self.position.start,
+                ..start
+            },
+        ))
+    }
+
+    /// Reads more lines of the input, after dropping the text already lexed.
+    ///
+    /// At least as much is read as is left to lex, so that a word spanning many lines
+    /// is matched again only a few times.
+    fn read_more(&mut self) {
+        self.text.drain(..self.consumed);
+        self.consumed = 0;
+        let len = 2 * self.text.len();
+        while !self.exhausted {
+            match self.reader.read_line(&mut self.text) {
+                Ok(0) => self.exhausted = true,
+                Ok(_) if self.text.len() >= len => return,
+                Ok(_) => (),
+                Err(err) => {
+                    self.exhausted = true;
+                    self.error = Some(err);
+                }
+            }
+        }
+    }
+
+    // NOTE: An unterminated string is not matched, and is lexed as an unknown word.
+    fn string_regex() -> &'static str {
+        r#""(?:[^"\\]|\\.)*""#
+    }
+
+    fn integer_regex() -> &'static str {
+        r"[+-]?[0-9]+"
+    }
+
+    /// Runs of any characters but spaces, parentheses, quotes, commas, bars and backslashes,
+    /// and of any characters between bars.
+    // NOTE: The characters outside bars must agree with `is_constituent`.
     fn ident_regex() -> &'static str {
-        r"[a-zA-Z][a-zA-Z0-9]*"
+        r#"(?:[^\s()'`,"|\\]|\|(?:[^|\\]|\\.)*\|)+"#
     }
 
     fn space_regex() -> &'static str {
         r"\s+"
     }
 }
+
+/// The characters of a string literal between its quotes, or `None` if an escape is unknown.
+fn unescape(s: &str) -> Option<String> {
+    let mut unescaped = String::new();
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        unescaped.push(match c {
+            '\\' => match chars.next()? {
+                '"' => '"',
+                '\\' => '\\',
+                'n' => '\n',
+                't' => '\t',
+                _ => return None,
+            },
+            c => c,
+        });
+    }
+    Some(unescaped)
+}
+
+/// The keyword that `s` is read as in `case_mode`, if any.
+///
+/// Keywords are written in lowercase, and are told apart regardless of case unless it is preserved.
+fn keyword(s: &str, case_mode: CaseMode) -> Option<Token> {
+    let s = match case_mode {
+        CaseMode::Preserve => s.to_string(),
+        CaseMode::Upcase | CaseMode::Downcase => CaseMode::Downcase.fold(s),
+    };
+    match s.as_str() {
+        "label" => Some(Token::Label),
+        "labels" => Some(Token::Labels),
+        "apply" => Some(Token::Apply),
+        "lambda" => Some(Token::Lambda),
+        "cond" => Some(Token::Cond),
+        "and" => Some(Token::And),
+        "or" => Some(Token::Or),
+        "not" => Some(Token::Not),
+        "if" => Some(Token::If),
+        "trace" => Some(Token::Trace),
+        "untrace" => Some(Token::Untrace),
+        "defmacro" => Some(Token::Defmacro),
+        _ => None,
+    }
+}
+
+/// Whether `name` is read as a keyword such as `lambda` in `case_mode` when written without bars.
+pub fn is_keyword(name: &str, case_mode: CaseMode) -> bool {
+    keyword(name, case_mode).is_some()
+}
+
+/// Whether `c` may be written in a symbol outside bars.
+fn is_constituent(c: char) -> bool {
+    !c.is_whitespace() && !"()'`,\"|\\".contains(c)
+}
+
+/// Whether the symbol `name` reads back as itself in `case_mode` when written without bars,
+/// as `CaseMode::unfold` writes it.
+///
+/// Otherwise it is written between bars, e.g. `|hello world|`, or `|12|` that is not an integer,
+/// or `|x|` when identifiers are read in uppercase.
+pub fn is_plain_symbol(name: &str, case_mode: CaseMode) -> bool {
+    !name.is_empty()
+        && name.chars().all(is_constituent)
+        && name.parse::<Integer>().is_err()
+        && case_mode.fold(name) == name
+}
+
+/// The name of a symbol as written, without its bars and the escapes between them,
+/// and with the letters outside bars read in `case_mode`.
+fn unescape_symbol(s: &str, case_mode: CaseMode) -> String {
+    if !s.contains('|') {
+        return case_mode.fold(s);
+    }
+    let mut name = String::new();
+    let mut chars = s.chars();
+    let mut barred = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '|' => barred = !barred,
+            '\\' if barred => name.extend(chars.next()),
+            c if barred => name.push(c),
+            c => name.push_str(&case_mode.fold_letters(&c.to_string())),
+        }
+    }
+    name
+}
@@ -1,6 +1,17 @@
+pub mod checker;
+pub mod debugger;
 pub mod environment;
+pub mod error;
+pub mod expander;
+pub mod heap;
+pub mod integer;
 pub mod interpreter;
 pub mod lexer;
 pub mod parser;
+pub mod prelude;
+pub mod primitive;
 pub mod syntax;
+pub mod termination;
 pub mod token;
+pub mod tracer;
+pub mod types;
@@ -1,9 +1,147 @@
-use std::io::{self, Write};
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{self, BufReader, Write};
+use std::process;
 
-use pure_lisp::{environment::Environment, lexer::Lexer, parser::Parser, syntax::Value};
+use pure_lisp::{
+    checker::{Checker, Lint},
+    debugger::Stepper,
+    environment::Environment,
+    error::{EvalError, TypeError},
+    expander::Expander,
+    heap::Heap,
+    interpreter::{BooleanMode, Context},
+    lexer::{CaseMode, Lexer},
+    parser::Parser,
+    prelude, primitive,
+    syntax::{Printed, Term, Value},
+    termination,
+    tracer::PrintTracer,
+    types::TypeChecker,
+};
+
+/// The number of the innermost applications printed with an error.
+const BACKTRACE_LIMIT: usize = 10;
+
+fn print_backtrace(err: &EvalError, case_mode: CaseMode) {
+    for frame in err.backtrace.iter().rev().take(BACKTRACE_LIMIT) {
+        match frame.span {
+            Some(span) => println!("  in {} at {}", Printed(case_mode, frame), span),
+            None => println!("  in {}", Printed(case_mode, frame)),
+        }
+    }
+    if err.backtrace.len() > BACKTRACE_LIMIT {
+        println!("  ... and {} more", err.backtrace.len() - BACKTRACE_LIMIT);
+    }
+}
+
+fn print_type_error(err: &TypeError) {
+    match err.span {
+        Some(span) => println!("Type error: {} at {}", err, span),
+        None => println!("Type error: {}", err),
+    }
+}
+
+/// Tells whether the recursive functions of a term, or the function bound to a variable, terminate.
+fn print_verdicts(term: &Term, env: &Environment) {
+    let term = match term.node() {
+        Term::Variable(name) => match env.lookup(name) {
+            Some(Value::Closure(closure)) => {
+                let lambda = Term::Lambda(closure.params.clone(), closure.body.clone());
+                match &closure.name {
+                    Some(label) => Term::Label(label.clone(), Box::new(lambda)),
+                    None => lambda,
+                }
+            }
+            _ => term.clone(),
+        },
+        _ => term.clone(),
+    };
+
+    let verdicts = termination::check(&term);
+    if verdicts.is_empty() {
+        println!("No function is defined by label or labels");
+    }
+    for verdict in verdicts {
+        if verdict.proven {
+            println!("{} terminates", verdict.name);
+        } else {
+            println!("Cannot prove that {} terminates", verdict.name);
+        }
+    }
+}
 
 fn main() -> io::Result<()> {
+    let args = std::env::args().collect::<Vec<_>>();
+    let has_flag = |flag: &str| args.iter().any(|arg| arg == flag);
+
+    let case_mode = if has_flag("--upcase") {
+        CaseMode::Upcase
+    } else if has_flag("--downcase") {
+        CaseMode::Downcase
+    } else {
+        CaseMode::Preserve
+    };
+
     let mut env = Environment::new();
+    if has_flag("--no-prelude") {
+        primitive::load_in(&mut env, case_mode);
+    } else {
+        prelude::load_in(&mut env, case_mode);
+    }
+    let mut expander = Expander::new();
+    let mut ctx = Context::new();
+    if has_flag("--strict") {
+        ctx.boolean_mode = BooleanMode::Strict;
+    } else if has_flag("--lisp15") {
+        ctx.boolean_mode = BooleanMode::Lisp15;
+    }
+    ctx.case_mode = case_mode;
+    ctx.tracer = Some(Box::new(PrintTracer(io::stdout(), case_mode)));
+    ctx.trace_all = has_flag("--trace");
+    if has_flag("--heap") {
+        ctx.heap = Some(Heap::new());
+    }
+
+    let types = TypeChecker::new(&env);
+    let checked = has_flag("--types").then_some(&types);
+
+    // NOTE: `--allow` takes the code of a lint as its value.
+    let mut allowed = HashSet::new();
+    let mut operands = vec![];
+    let mut rest = args.iter().skip(1);
+    while let Some(arg) = rest.next() {
+        if arg == "--allow" {
+            let code = rest.next().map_or("", String::as_str);
+            match Lint::from_code(code) {
+                Some(lint) => allowed.insert(lint),
+                None => {
+                    eprintln!("Unknown lint code `{}`", code);
+                    process::exit(2);
+                }
+            };
+        } else if !arg.starts_with("--") {
+            operands.push(arg.as_str());
+        }
+    }
+
+    match operands.as_slice() {
+        [] => (),
+        ["check", path] => {
+            let mut checker = Checker::new(&env);
+            checker.allowed = allowed;
+            checker.case_mode = case_mode;
+            if !check_file(path, &checker, case_mode)? {
+                process::exit(1);
+            }
+            return Ok(());
+        }
+        [path] => return run_file(path, &mut expander, &mut env, &mut ctx, checked, case_mode),
+        _ => {
+            eprintln!("Usage: pure-lisp [FLAGS] [check] [FILE]");
+            process::exit(2);
+        }
+    }
 
     loop {
         print!("> ");
@@ -15,20 +153,154 @@ fn main() -> io::Result<()> {
             return Ok(());
         }
 
-        match Parser::new(Lexer::new(&input.to_string())).parse() {
-            Some(term) => match term.eval(&mut env) {
-                Some(value) => println!("{}", to_string_value(&value)),
-                None => println!("Semantics error"),
-            },
-            None => println!("Syntax error"),
+        if input.trim() == ":gc" || input.trim() == ":stats" {
+            match &mut ctx.heap {
+                Some(heap) => {
+                    if input.trim() == ":gc" {
+                        println!("Reclaimed {} cell(s)", heap.collect(&env));
+                    }
+                    let stats = heap.stats();
+                    println!(
+                        "live cells: {}, allocations: {}, collections: {}, cells reclaimed: {}, bytes reclaimed: {}",
+                        stats.live_cells,
+                        stats.allocations,
+                        stats.collections,
+                        stats.cells_reclaimed,
+                        stats.bytes_reclaimed
+                    );
+                }
+                None => println!("The heap is disabled (run with --heap)"),
+            }
+            continue;
+        }
+
+        if let Some(input) = input.trim_start().strip_prefix(":type") {
+            match Parser::new(Lexer::with_case_mode(input, case_mode)).next_term() {
+                Some(Ok(term)) => match expander.expand(term) {
+                    Ok(term) => match types.infer(&term) {
+                        Ok(ty) => println!("{}", ty),
+                        Err(err) => print_type_error(&err),
+                    },
+                    Err(err) => println!("Macro expansion error: {}", Printed(case_mode, &err)),
+                },
+                Some(Err(err)) => println!("Syntax error: {} at {}", err, err.span),
+                None => (),
+            }
+            continue;
+        }
+
+        if let Some(input) = input.trim_start().strip_prefix(":terminates") {
+            match Parser::new(Lexer::with_case_mode(input, case_mode)).next_term() {
+                Some(Ok(term)) => match expander.expand(term) {
+                    Ok(term) => print_verdicts(&term, &env),
+                    Err(err) => println!("Macro expansion error: {}", Printed(case_mode, &err)),
+                },
+                Some(Err(err)) => println!("Syntax error: {} at {}", err, err.span),
+                None => (),
+            }
+            continue;
+        }
+
+        if let Some(input) = input.trim_start().strip_prefix(":expand") {
+            match Parser::new(Lexer::with_case_mode(input, case_mode)).parse() {
+                Some(term) => match expander.expand(term) {
+                    Ok(term) => println!("{}", Printed(case_mode, &term)),
+                    Err(err) => println!("Macro expansion error: {}", Printed(case_mode, &err)),
+                },
+                None => println!("Syntax error"),
+            }
+            continue;
+        }
+
+        // NOTE: `:debug term` evaluates the term step by step.
+        let debug = input.trim_start().strip_prefix(":debug");
+        if debug.is_some() {
+            ctx.debugger = Some(Box::new(Stepper::new(io::stdin().lock(), io::stdout())));
+        }
+
+        match Parser::new(Lexer::with_case_mode(debug.unwrap_or(&input), case_mode)).next_term() {
+            Some(Ok(term)) => eval(term, &mut expander, &mut env, &mut ctx, checked, case_mode),
+            Some(Err(err)) => println!("Syntax error: {} at {}", err, err.span),
+            None => (),
+        }
+        ctx.debugger = None;
+    }
+}
+
+/// Reports the syntax errors and the warnings of a file term by term as it is read,
+/// and tells whether there were none.
+fn check_file(path: &str, checker: &Checker, case_mode: CaseMode) -> io::Result<bool> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut clean = true;
+    for result in Parser::new(Lexer::from_reader(reader, case_mode)) {
+        let term = match result {
+            Ok(term) => term,
+            Err(err) => {
+                println!("{}:{}: Syntax error: {}", path, err.span, err);
+                clean = false;
+                continue;
+            }
+        };
+        for warning in checker.check(&term) {
+            match warning.span {
+                Some(span) => println!("{}:{}: {}", path, span, warning),
+                None => println!("{}: {}", path, warning),
+            }
+            clean = false;
         }
     }
+    Ok(clean)
 }
 
-fn to_string_value(value: &Value) -> String {
-    match value {
-        Value::Symbol(s) => s.clone(),
-        Value::Pair(car, cdr) => format!("({} {})", to_string_value(car), to_string_value(cdr)),
-        Value::Closure(_, _, _) => "<closure>".to_string(),
+/// Evaluates every term of a file as it is read, reporting the syntax errors where they are found.
+fn run_file(
+    path: &str,
+    expander: &mut Expander,
+    env: &mut Environment,
+    ctx: &mut Context,
+    types: Option<&TypeChecker>,
+    case_mode: CaseMode,
+) -> io::Result<()> {
+    let reader = BufReader::new(File::open(path)?);
+    for result in Parser::new(Lexer::from_reader(reader, case_mode)) {
+        match result {
+            Ok(term) => eval(term, expander, env, ctx, types, case_mode),
+            Err(err) => println!("{}:{}: Syntax error: {}", path, err.span, err),
+        }
     }
+    Ok(())
+}
+
+/// Evaluates a term, after checking its type if `types` is given.
+fn eval(
+    term: Term,
+    expander: &mut Expander,
+    env: &mut Environment,
+    ctx: &mut Context,
+    types: Option<&TypeChecker>,
+    case_mode: CaseMode,
+) {
+    let term = match expander.expand(term) {
+        Ok(term) => term,
+        Err(err) => {
+            println!("Macro expansion error: {}", Printed(case_mode, &err));
+            return;
+        }
+    };
+    if let Some(types) = types {
+        if let Err(err) = types.infer(&term) {
+            print_type_error(&err);
+            return;
+        }
+    }
+
+    match term.eval_with(env, ctx) {
+        Ok(value) => println!("{}", Printed(case_mode, &ctx.export(&value))),
+        Err(err) => {
+            println!("Semantics error: {}", Printed(case_mode, &err));
+            print_backtrace(&err, case_mode);
+        }
+    }
+
+    ctx.collect_garbage(env);
 }
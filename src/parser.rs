@@ -1,13 +1,17 @@
+use std::io;
+
 use crate::{
+    error::{ParseError, ParseErrorKind},
     lexer::Lexer,
+    primitive,
     syntax::{SExpression, Term},
     token::Token,
 };
 
-pub struct Parser(Lexer);
+pub struct Parser<'a>(Lexer<'a>);
 
-impl Parser {
-    pub fn new(lex: Lexer) -> Self {
+impl<'a> Parser<'a> {
+    pub fn new(lex: Lexer<'a>) -> Self {
         Parser(lex)
     }
 
@@ -15,11 +19,61 @@ impl Parser {
         self.parse_term()
     }
 
+    /// Parses the next term, or gives `None` if only spaces are left.
+    pub fn next_term(&mut self) -> Option<Result<Term, ParseError>> {
+        if self.0.at_end() {
+            return self.0.take_error().map(|err| Err(self.io_error(err)));
+        }
+
+        let term = self.parse_term();
+        Some(term.ok_or_else(|| match self.0.take_error() {
+            Some(err) => self.io_error(err),
+            None => ParseError {
+                kind: match self.0.word() {
+                    Some(word) => ParseErrorKind::UnexpectedToken(word.to_string()),
+                    None => ParseErrorKind::UnexpectedEnd,
+                },
+                span: self.0.span(),
+            },
+        }))
+    }
+
+    /// Parses every term up to the end of the input.
+    ///
+    /// After a syntax error, parsing goes on from the next top-level term,
+    /// so that all the errors are found at once.
+    pub fn parse_all(&mut self) -> (Vec<Term>, Vec<ParseError>) {
+        let mut terms = vec![];
+        let mut errors = vec![];
+        for result in self {
+            match result {
+                Ok(term) => terms.push(term),
+                Err(err) => errors.push(err),
+            }
+        }
+        (terms, errors)
+    }
+
+    fn io_error(&self, err: io::Error) -> ParseError {
+        ParseError {
+            kind: ParseErrorKind::Io(err.to_string()),
+            span: self.0.span(),
+        }
+    }
+
     fn parse_term(&mut self) -> Option<Term> {
-        let Some(token) = self.0.next_token() else {
-            return None;
-        };
+        let token = self.0.next_token()?;
+        self.parse_term_from(token)
+    }
+
+    /// Parses a term starting with `token`, which has just been read.
+    fn parse_term_from(&mut self, token: Token) -> Option<Term> {
+        let start = self.0.span();
+        let term = self.parse_node_from(token)?;
+        Some(Term::Spanned(start.to(self.0.span()), Box::new(term)))
+    }
 
+    fn parse_node_from(&mut self, token: Token) -> Option<Term> {
         match token {
             Token::LParen => {
                 let token = self.0.next_token()?;
@@ -50,41 +104,31 @@ impl Parser {
                             }
                         }
                     }
-                    Token::Atom => {
-                        let term1 = self.parse_term()?;
-                        match self.0.next_token()? {
-                            Token::RParen => Some(Term::Atom(Box::new(term1))),
-                            _ => None,
-                        }
-                    }
-                    Token::Eq => {
-                        let term1 = self.parse_term()?;
-                        let term2 = self.parse_term()?;
-                        match self.0.next_token()? {
-                            Token::RParen => Some(Term::Eq(Box::new(term1), Box::new(term2))),
-                            _ => None,
-                        }
-                    }
-                    Token::Car => {
-                        let term = self.parse_term()?;
-                        match self.0.next_token()? {
-                            Token::RParen => Some(Term::Car(Box::new(term))),
-                            _ => None,
-                        }
-                    }
-                    Token::Cdr => {
+                    Token::And => Some(Term::And(self.parse_terms()?)),
+                    Token::Or => Some(Term::Or(self.parse_terms()?)),
+                    Token::Not => {
                         let term = self.parse_term()?;
                         match self.0.next_token()? {
-                            Token::RParen => Some(Term::Cdr(Box::new(term))),
+                            Token::RParen => Some(Term::Not(Box::new(term))),
                             _ => None,
                         }
                     }
-                    Token::Cons => {
+                    Token::If => {
                         let term1 = self.parse_term()?;
                         let term2 = self.parse_term()?;
                         match self.0.next_token()? {
-                            Token::RParen => Some(Term::Cons(Box::new(term1), Box::new(term2))),
-                            _ => None,
+                            Token::RParen => Some(Term::If(Box::new(term1), Box::new(term2), None)),
+                            token => {
+                                let term3 = self.parse_term_from(token)?;
+                                match self.0.next_token()? {
+                                    Token::RParen => Some(Term::If(
+                                        Box::new(term1),
+                                        Box::new(term2),
+                                        Some(Box::new(term3)),
+                                    )),
+                                    _ => None,
+                                }
+                            }
                         }
                     }
                     Token::Lambda => {
@@ -126,6 +170,12 @@ impl Parser {
                         loop {
                             match self.0.next_token()? {
                                 Token::Comma => (),
+                                Token::CommaAt => {
+                                    args.push(Box::new(Term::UnquoteSplicing(Box::new(
+                                        self.parse_term()?,
+                                    ))));
+                                    continue;
+                                }
                                 Token::RParen => {
                                     break;
                                 }
@@ -147,29 +197,176 @@ impl Parser {
                         let token = self.0.next_token()?;
                         let term = self.parse_term()?;
 
-                        match token {
-                            Token::Id(id) if self.0.next_token()? == Token::RParen => {
+                        match name(token) {
+                            Some(id) if self.0.next_token()? == Token::RParen => {
                                 Some(Term::Label(id, Box::new(term)))
                             }
                             _ => None,
                         }
                     }
+                    Token::Labels => {
+                        if self.0.next_token()? != Token::LParen {
+                            return None;
+                        }
+
+                        let mut bindings = vec![];
+                        loop {
+                            match self.0.next_token()? {
+                                Token::LParen => {
+                                    let Token::Id(id) = self.0.next_token()? else {
+                                        return None;
+                                    };
+                                    let term = self.parse_term()?;
+
+                                    if self.0.next_token()? == Token::RParen {
+                                        bindings.push((id, Box::new(term)));
+                                    } else {
+                                        return None;
+                                    }
+                                }
+                                Token::RParen => {
+                                    break;
+                                }
+                                _ => {
+                                    return None;
+                                }
+                            }
+                        }
+
+                        let term = self.parse_term()?;
+                        match self.0.next_token()? {
+                            Token::RParen => Some(Term::Labels(bindings, Box::new(term))),
+                            _ => None,
+                        }
+                    }
+                    Token::Trace => {
+                        let Token::Id(name) = self.0.next_token()? else {
+                            return None;
+                        };
+                        match self.0.next_token()? {
+                            Token::RParen => Some(Term::Trace(name)),
+                            _ => None,
+                        }
+                    }
+                    Token::Untrace => {
+                        let Token::Id(name) = self.0.next_token()? else {
+                            return None;
+                        };
+                        match self.0.next_token()? {
+                            Token::RParen => Some(Term::Untrace(name)),
+                            _ => None,
+                        }
+                    }
+                    Token::Defmacro => {
+                        let Token::Id(name) = self.0.next_token()? else {
+                            return None;
+                        };
+                        if self.0.next_token()? != Token::LParen {
+                            return None;
+                        }
+
+                        let mut params = vec![];
+                        loop {
+                            match self.0.next_token()? {
+                                Token::Id(id) => {
+                                    params.push(id);
+                                }
+                                Token::RParen => {
+                                    break;
+                                }
+                                _ => {
+                                    return None;
+                                }
+                            }
+                        }
+
+                        let term = self.parse_term()?;
+                        match self.0.next_token()? {
+                            Token::RParen => Some(Term::Defmacro(name, params, Box::new(term))),
+                            _ => None,
+                        }
+                    }
+                    Token::Id(name) => match primitive::lookup_in(&name, self.0.case_mode()) {
+                        Some(primitive) if primitive.special_form => {
+                            let mut args = vec![];
+                            for _ in 0..primitive.arity {
+                                args.push(Box::new(self.parse_term()?));
+                            }
+                            match self.0.next_token()? {
+                                Token::RParen => Some(Term::Primitive(primitive, args)),
+                                _ => None,
+                            }
+                        }
+                        _ => {
+                            let mut args = vec![];
+                            loop {
+                                match self.0.next_token()? {
+                                    Token::RParen => {
+                                        break;
+                                    }
+                                    Token::CommaAt => {
+                                        args.push(Box::new(Term::UnquoteSplicing(Box::new(
+                                            self.parse_term()?,
+                                        ))));
+                                    }
+                                    token => {
+                                        args.push(Box::new(self.parse_term_from(token)?));
+                                    }
+                                }
+                            }
+
+                            Some(Term::MacroCall(name, args))
+                        }
+                    },
                     _ => None,
                 }
             }
-            Token::Id(id) => Some(Term::Variable(id)),
+            token @ (Token::Id(_) | Token::And | Token::Or | Token::Not) => {
+                name(token).map(Term::Variable)
+            }
+            // NOTE: An integer or a string evaluates to itself, as if it were quoted.
+            Token::Integer(n) => Some(Term::Quote(SExpression::Integer(n))),
+            Token::String(s) => Some(Term::Quote(SExpression::String(s))),
             Token::Quote => Some(Term::Quote(self.parse_sexpression()?)),
+            Token::Backquote => Some(Term::Quasiquote(Box::new(self.parse_term()?))),
+            Token::Comma => Some(Term::Unquote(Box::new(self.parse_term()?))),
             _ => None,
         }
     }
 
+    /// Parses terms up to the closing parenthesis.
+    // NOTE: The terms are boxed as in `Term::And` and `Term::Or`.
+    #[allow(clippy::vec_box)]
+    fn parse_terms(&mut self) -> Option<Vec<Box<Term>>> {
+        let mut terms = vec![];
+        loop {
+            match self.0.next_token()? {
+                Token::RParen => return Some(terms),
+                token => terms.push(Box::new(self.parse_term_from(token)?)),
+            }
+        }
+    }
+
     fn parse_sexpression(&mut self) -> Option<SExpression> {
-        let Some(token) = self.0.next_token() else {
-            return None;
-        };
+        let token = self.0.next_token()?;
 
         match token {
             Token::Id(id) => Some(SExpression::Symbol(id)),
+            // NOTE: A keyword is a symbol like any other in an S-expression.
+            Token::Cond
+            | Token::And
+            | Token::Or
+            | Token::Not
+            | Token::If
+            | Token::Lambda
+            | Token::Apply
+            | Token::Label
+            | Token::Labels
+            | Token::Trace
+            | Token::Untrace
+            | Token::Defmacro => Some(SExpression::Symbol(self.0.case_mode().fold(self.0.word()?))),
+            Token::Integer(n) => Some(SExpression::Integer(n)),
+            Token::String(s) => Some(SExpression::String(s)),
             Token::LParen => {
                 let exp1 = self.parse_sexpression()?;
                 let exp2 = self.parse_sexpression()?;
@@ -182,3 +379,31 @@ impl Parser {
         }
     }
 }
+
+/// Parses the terms one by one as the input is read, going on after a syntax error
+/// from the next top-level term.
+impl Iterator for Parser<'_> {
+    type Item = Result<Term, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let result = self.next_term()?;
+        if result.is_err() {
+            self.0.recover();
+        }
+        Some(result)
+    }
+}
+
+/// The name of a variable that `token` stands for, if any.
+///
+/// `and`, `or` and `not` are forms only at the head of a list, and elsewhere name
+/// the functions of the prelude, e.g. in `(apply and (,p ,q))`.
+fn name(token: Token) -> Option<String> {
+    match token {
+        Token::Id(id) => Some(id),
+        Token::And => Some("and".to_string()),
+        Token::Or => Some("or".to_string()),
+        Token::Not => Some("not".to_string()),
+        _ => None,
+    }
+}
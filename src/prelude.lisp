@@ -0,0 +1,60 @@
+(label ff (lambda (x)
+  (cond ((atom x) x)
+        ('T (apply ff (,(car x)))))))
+
+(label subst (lambda (x y z)
+  (cond ((atom z) (cond ((eq z y) x)
+                        ('T z)))
+        ('T (cons (apply subst (,x ,y ,(car z)))
+                  (apply subst (,x ,y ,(cdr z))))))))
+
+(label equal (lambda (x y)
+  (cond ((atom x) (cond ((atom y) (eq x y))
+                        ('T (eq 'A 'B))))
+        ((atom y) (eq 'A 'B))
+        ((apply equal (,(car x) ,(car y))) (apply equal (,(cdr x) ,(cdr y))))
+        ('T (eq 'A 'B)))))
+
+(label null (lambda (x)
+  (cond ((atom x) (eq x 'NIL))
+        ('T (eq 'A 'B)))))
+
+(label and (lambda (p q)
+  (cond (p q)
+        ('T (eq 'A 'B)))))
+
+(label or (lambda (p q)
+  (cond (p 'T)
+        ('T q))))
+
+(label not (lambda (p)
+  (cond (p (eq 'A 'B))
+        ('T 'T))))
+
+(label append (lambda (x y)
+  (cond ((apply null (,x)) y)
+        ('T (cons (car x) (apply append (,(cdr x) ,y)))))))
+
+(label among (lambda (x y)
+  (cond ((apply null (,y)) (eq 'A 'B))
+        ((apply equal (,x ,(car y))) 'T)
+        ('T (apply among (,x ,(cdr y)))))))
+
+(label pair (lambda (x y)
+  (cond ((apply null (,x)) (cond ((apply null (,y)) 'NIL)))
+        ('T (cons (cons (car x) (cons (car y) 'NIL))
+                  (apply pair (,(cdr x) ,(cdr y))))))))
+
+(label assoc (lambda (x y)
+  (cond ((eq (car (car y)) x) (car (cdr (car y))))
+        ('T (apply assoc (,x ,(cdr y)))))))
+
+(label sub2 (lambda (x z)
+  (cond ((apply null (,x)) z)
+        ((eq (car (car x)) z) (car (cdr (car x))))
+        ('T (apply sub2 (,(cdr x) ,z))))))
+
+(label sublis (lambda (x y)
+  (cond ((atom y) (apply sub2 (,x ,y)))
+        ('T (cons (apply sublis (,x ,(car y)))
+                  (apply sublis (,x ,(cdr y))))))))
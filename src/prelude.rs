@@ -0,0 +1,39 @@
+use crate::{
+    environment::Environment,
+    lexer::{CaseMode, Lexer},
+    parser::Parser,
+    primitive,
+    syntax::Term,
+};
+
+/// The functions defined in the paper, each written as a `label` expression.
+///
+/// They give falsity as `(eq 'A 'B)` rather than `'F`, so that it is false in every boolean mode,
+/// e.g. `NIL` in LISP 1.5.
+pub const SOURCE: &str = include_str!("prelude.lisp");
+
+/// Binds every function of the prelude to its name, after the primitives.
+pub fn load(env: &mut Environment) {
+    load_in(env, CaseMode::Preserve);
+}
+
+/// Binds every function of the prelude as `load` does, under its name as read in `case_mode`.
+pub fn load_in(env: &mut Environment, case_mode: CaseMode) {
+    primitive::load_in(env, case_mode);
+    // NOTE: The prelude names its functions in lowercase and writes its atoms in uppercase,
+    // so it is read as written unless identifiers are read in uppercase.
+    let case_mode = match case_mode {
+        CaseMode::Upcase => CaseMode::Upcase,
+        CaseMode::Preserve | CaseMode::Downcase => CaseMode::Preserve,
+    };
+    let mut parser = Parser::new(Lexer::with_case_mode(SOURCE, case_mode));
+
+    while let Some(term) = parser.parse() {
+        let Term::Label(name, _) = term.node() else {
+            panic!("The prelude should only contain `label` expressions");
+        };
+        let name = name.clone();
+        let value = term.eval(env).expect("The prelude should be evaluated");
+        env.extend(name, value);
+    }
+}
@@ -0,0 +1,393 @@
+use std::{cell::RefCell, fmt, rc::Rc};
+
+use crate::{
+    environment::Environment,
+    error::ErrorKind,
+    integer::Integer,
+    interpreter::Context,
+    lexer::CaseMode,
+    syntax::{Captured, Closure, Term, Value},
+};
+
+/// A function whose implementation is given to the interpreter, together with how it is written.
+///
+/// The parser and the evaluator look primitives up by name in `PRIMITIVES` and `ACCESSORS`,
+/// so that adding one needs no new token, term or evaluation rule.
+// NOTE: `Debug` and `PartialEq` are written by hand, since functions can be neither printed
+// nor compared. Primitives are told apart by their names.
+pub struct Primitive {
+    pub name: &'static str,
+    pub arity: usize,
+    /// Whether `(name args...)` is a form of its own, as with the elementary S-functions.
+    /// Otherwise the primitive is only applied through the variable bound to it.
+    pub special_form: bool,
+    /// Receives exactly `arity` evaluated arguments.
+    pub function: fn(&mut Context, Vec<Value>) -> Result<Value, ErrorKind>,
+}
+
+impl fmt::Debug for Primitive {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Primitive")
+            .field("name", &self.name)
+            .field("arity", &self.arity)
+            .field("special_form", &self.special_form)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for Primitive {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Eq for Primitive {}
+
+/// Every primitive, in the order they are bound.
+pub const PRIMITIVES: &[Primitive] = &[
+    /* The Elementary S-functions and Predicates */
+    Primitive {
+        name: "atom",
+        arity: 1,
+        special_form: true,
+        function: |ctx, args| {
+            let [value] = take(args);
+            Ok(ctx.truth(is_atom(&value)))
+        },
+    },
+    Primitive {
+        name: "eq",
+        arity: 2,
+        special_form: true,
+        function: |ctx, args| match take(args) {
+            [Value::Symbol(str1), Value::Symbol(str2)]
+            | [Value::String(str1), Value::String(str2)] => Ok(ctx.truth(str1 == str2)),
+            [Value::Integer(n1), Value::Integer(n2)] => Ok(ctx.truth(n1 == n2)),
+            // NOTE: Atoms of different kinds are never equal.
+            [value1, value2] if is_atom(&value1) && is_atom(&value2) => Ok(ctx.truth(false)),
+            [value1, value2] => {
+                let value = if is_atom(&value1) { value2 } else { value1 };
+                Err(ErrorKind::NotAnAtom(ctx.export(&value)))
+            }
+        },
+    },
+    Primitive {
+        name: "car",
+        arity: 1,
+        special_form: true,
+        function: |ctx, args| {
+            let [value] = take(args);
+            match ctx.uncons(value) {
+                Ok((fst, _)) => Ok(fst),
+                Err(value) => Err(ErrorKind::NotAPair("car", value)),
+            }
+        },
+    },
+    Primitive {
+        name: "cdr",
+        arity: 1,
+        special_form: true,
+        function: |ctx, args| {
+            let [value] = take(args);
+            match ctx.uncons(value) {
+                Ok((_, snd)) => Ok(snd),
+                Err(value) => Err(ErrorKind::NotAPair("cdr", value)),
+            }
+        },
+    },
+    Primitive {
+        name: "cons",
+        arity: 2,
+        special_form: true,
+        function: |ctx, args| {
+            let [value1, value2] = take(args);
+            Ok(ctx.cons(value1, value2))
+        },
+    },
+    /* Arithmetic */
+    Primitive {
+        name: "+",
+        arity: 2,
+        special_form: true,
+        function: |ctx, args| {
+            let [n1, n2] = integers(ctx, "+", args)?;
+            Ok(Value::Integer(&n1 + &n2))
+        },
+    },
+    Primitive {
+        name: "-",
+        arity: 2,
+        special_form: true,
+        function: |ctx, args| {
+            let [n1, n2] = integers(ctx, "-", args)?;
+            Ok(Value::Integer(&n1 - &n2))
+        },
+    },
+    Primitive {
+        name: "*",
+        arity: 2,
+        special_form: true,
+        function: |ctx, args| {
+            let [n1, n2] = integers(ctx, "*", args)?;
+            Ok(Value::Integer(&n1 * &n2))
+        },
+    },
+    Primitive {
+        name: "quotient",
+        arity: 2,
+        special_form: true,
+        function: |ctx, args| {
+            let [n1, n2] = integers(ctx, "quotient", args)?;
+            match n1.div_rem(&n2) {
+                Some((quotient, _)) => Ok(Value::Integer(quotient)),
+                None => Err(ErrorKind::DivisionByZero("quotient")),
+            }
+        },
+    },
+    Primitive {
+        name: "remainder",
+        arity: 2,
+        special_form: true,
+        function: |ctx, args| {
+            let [n1, n2] = integers(ctx, "remainder", args)?;
+            match n1.div_rem(&n2) {
+                Some((_, remainder)) => Ok(Value::Integer(remainder)),
+                None => Err(ErrorKind::DivisionByZero("remainder")),
+            }
+        },
+    },
+    Primitive {
+        name: "<",
+        arity: 2,
+        special_form: true,
+        function: |ctx, args| {
+            let [n1, n2] = integers(ctx, "<", args)?;
+            Ok(ctx.truth(n1 < n2))
+        },
+    },
+    Primitive {
+        name: "=",
+        arity: 2,
+        special_form: true,
+        function: |ctx, args| {
+            let [n1, n2] = integers(ctx, "=", args)?;
+            Ok(ctx.truth(n1 == n2))
+        },
+    },
+    Primitive {
+        name: ">",
+        arity: 2,
+        special_form: true,
+        function: |ctx, args| {
+            let [n1, n2] = integers(ctx, ">", args)?;
+            Ok(ctx.truth(n1 > n2))
+        },
+    },
+    Primitive {
+        name: "numberp",
+        arity: 1,
+        special_form: true,
+        function: |ctx, args| {
+            let [value] = take(args);
+            Ok(ctx.truth(matches!(value, Value::Integer(_))))
+        },
+    },
+    /* Strings and Symbols */
+    Primitive {
+        name: "string-append",
+        arity: 2,
+        special_form: true,
+        function: |ctx, args| match take(args) {
+            [Value::String(str1), Value::String(str2)] => Ok(Value::String(str1 + &str2)),
+            [Value::String(_), value] | [value, _] => {
+                Err(ErrorKind::NotAString("string-append", ctx.export(&value)))
+            }
+        },
+    },
+    Primitive {
+        name: "symbol->string",
+        arity: 1,
+        special_form: true,
+        function: |ctx, args| match take(args) {
+            [Value::Symbol(name)] => Ok(Value::String(name)),
+            [value] => Err(ErrorKind::NotASymbol("symbol->string", ctx.export(&value))),
+        },
+    },
+    Primitive {
+        name: "string->symbol",
+        arity: 1,
+        special_form: true,
+        function: |ctx, args| match take(args) {
+            [Value::String(s)] => Ok(Value::Symbol(s)),
+            [value] => Err(ErrorKind::NotAString("string->symbol", ctx.export(&value))),
+        },
+    },
+    Primitive {
+        name: "explode",
+        arity: 1,
+        special_form: true,
+        function: |ctx, args| match take(args) {
+            [Value::Symbol(name)] => {
+                let mut list = Value::Symbol("NIL".to_string());
+                for c in name.chars().rev() {
+                    list = ctx.cons(Value::Symbol(c.to_string()), list);
+                }
+                Ok(list)
+            }
+            [value] => Err(ErrorKind::NotASymbol("explode", ctx.export(&value))),
+        },
+    },
+    Primitive {
+        name: "implode",
+        arity: 1,
+        special_form: true,
+        function: |ctx, args| {
+            let [mut list] = take(args);
+            let mut name = String::new();
+            loop {
+                match ctx.uncons(list) {
+                    Ok((Value::Symbol(c), rest)) if c.chars().count() == 1 => {
+                        name.push_str(&c);
+                        list = rest;
+                    }
+                    Ok((value, _)) => return Err(ErrorKind::NotACharacter(ctx.export(&value))),
+                    Err(Value::Symbol(nil)) if nil == "NIL" => break,
+                    Err(value) => return Err(ErrorKind::NotAList(ctx.export(&value))),
+                }
+            }
+            // NOTE: The empty list makes the empty symbol `||`, so that `implode` undoes `explode`.
+            Ok(Value::Symbol(name))
+        },
+    },
+];
+
+/// Makes an accessor for each name, which takes the `car` for each `a` and the `cdr` for each
+/// `d` between `c` and `r`, from right to left.
+macro_rules! accessors {
+    ($($name:ident)*) => {
+        &[$(Primitive {
+            name: stringify!($name),
+            arity: 1,
+            special_form: true,
+            function: |ctx, args| {
+                let [value] = take(args);
+                access(ctx, lookup(stringify!($name)).unwrap(), value)
+            },
+        }),*]
+    };
+}
+
+/// The compositions of two to four `car`s and `cdr`s, such as `(cadr x)` for `(car (cdr x))`.
+pub const ACCESSORS: &[Primitive] = accessors![
+    caar cadr cdar cddr
+    caaar caadr cadar caddr cdaar cdadr cddar cdddr
+    caaaar caaadr caadar caaddr cadaar cadadr caddar cadddr
+    cdaaar cdaadr cdadar cdaddr cddaar cddadr cdddar cddddr
+];
+
+/// The primitive named `name`, if any.
+pub fn lookup(name: &str) -> Option<&'static Primitive> {
+    PRIMITIVES
+        .iter()
+        .chain(ACCESSORS)
+        .find(|primitive| primitive.name == name)
+}
+
+/// The primitive that `name` is read as in `case_mode`, e.g. `car` for `CAR` when identifiers
+/// are read in uppercase.
+pub fn lookup_in(name: &str, case_mode: CaseMode) -> Option<&'static Primitive> {
+    PRIMITIVES
+        .iter()
+        .chain(ACCESSORS)
+        .find(|primitive| case_mode.fold(primitive.name) == name)
+}
+
+/// The `a`s and `d`s of `name` if it is `car`, `cdr` or one of the `ACCESSORS`.
+pub fn accessor_path(name: &str) -> Option<&str> {
+    let path = name.strip_prefix('c')?.strip_suffix('r')?;
+    (!path.is_empty() && path.len() <= 4 && path.bytes().all(|c| c == b'a' || c == b'd'))
+        .then_some(path)
+}
+
+/// Applies the steps of an accessor to `value`, innermost first.
+fn access(
+    ctx: &mut Context,
+    accessor: &'static Primitive,
+    mut value: Value,
+) -> Result<Value, ErrorKind> {
+    let path = accessor_path(accessor.name).expect("An accessor should be named c[ad]+r");
+    for (index, c) in path.bytes().rev().enumerate() {
+        value = match ctx.uncons(value) {
+            Ok((fst, _)) if c == b'a' => fst,
+            Ok((_, snd)) => snd,
+            Err(value) => {
+                return Err(ErrorKind::NotAPairInAccessor {
+                    accessor,
+                    step: index + 1,
+                    value,
+                })
+            }
+        };
+    }
+    Ok(value)
+}
+
+/// Binds every primitive to its name, so that it can be passed around.
+///
+/// Each of them is a closure whose body is the primitive applied to its parameters.
+pub fn load(env: &mut Environment) {
+    load_in(env, CaseMode::Preserve);
+}
+
+/// Binds every primitive as `load` does, under its name as read in `case_mode`.
+pub fn load_in(env: &mut Environment, case_mode: CaseMode) {
+    for primitive in PRIMITIVES.iter().chain(ACCESSORS) {
+        let name = case_mode.fold(primitive.name);
+        let params = (1..=primitive.arity)
+            .map(|index| format!("x{}", index))
+            .collect::<Vec<_>>();
+        let args = params
+            .iter()
+            .map(|param| Box::new(Term::Variable(param.clone())))
+            .collect();
+        let closure = Closure {
+            name: Some(name.clone()),
+            params,
+            body: Box::new(Term::Primitive(primitive, args)),
+            env: Captured::Strong(Rc::new(RefCell::new(Environment::new()))),
+        };
+        env.extend(name, Value::Closure(closure));
+    }
+}
+
+/// Whether `value` is an atom, i.e. anything but a pair or a function.
+fn is_atom(value: &Value) -> bool {
+    matches!(
+        value,
+        Value::Symbol(_) | Value::Integer(_) | Value::String(_)
+    )
+}
+
+/// The arguments of a primitive, whose number the evaluator has already checked.
+fn take<const N: usize>(args: Vec<Value>) -> [Value; N] {
+    args.try_into()
+        .expect("A primitive should be given as many arguments as its arity")
+}
+
+/// The arguments of the arithmetic primitive `name`, which must all be integers.
+fn integers<const N: usize>(
+    ctx: &Context,
+    name: &'static str,
+    args: Vec<Value>,
+) -> Result<[Integer; N], ErrorKind> {
+    let mut integers = vec![];
+    for arg in take::<N>(args) {
+        match arg {
+            Value::Integer(n) => integers.push(n),
+            value => return Err(ErrorKind::NotAnInteger(name, ctx.export(&value))),
+        }
+    }
+    Ok(integers
+        .try_into()
+        .expect("There should be as many integers as arguments"))
+}
@@ -1,35 +1,578 @@
-use crate::environment::Environment;
-use std::{cell::RefCell, rc::Rc};
+use crate::{
+    environment::Environment,
+    error::ErrorKind,
+    integer::Integer,
+    lexer::{self, CaseMode},
+    primitive::Primitive,
+};
+use std::{
+    cell::RefCell,
+    fmt,
+    rc::{Rc, Weak},
+};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Value {
     Symbol(String),
+    Integer(Integer),
+    String(String),
     Pair(Box<Value>, Box<Value>),
-    Closure(Vec<String>, Box<Term>, Rc<RefCell<Environment>>),
+    Closure(Closure),
+    Builtin(Builtin),
+    Code(Box<Term>),
+    /// A pair allocated on a `Heap`.
+    Cell(usize),
 }
 
+// NOTE: `Debug` and `PartialEq` are written by hand, since the environment of a closure made by
+// `label` refers back to the closure. Environments are only compared by identity and never printed.
+#[derive(Clone)]
+pub struct Closure {
+    /// The name given by `label` or `labels`, if any.
+    pub name: Option<String>,
+    pub params: Vec<String>,
+    pub body: Box<Term>,
+    pub env: Captured,
+}
+
+/// The environment captured by a closure.
+///
+/// A closure made by `label` (or `labels`) is also bound in its own environment.
+/// That binding refers back to the environment weakly, so that no reference cycle keeps it alive.
+#[derive(Clone)]
+pub enum Captured {
+    Strong(Rc<RefCell<Environment>>),
+    Weak(Weak<RefCell<Environment>>),
+}
+
+impl Captured {
+    pub fn upgrade(&self) -> Option<Rc<RefCell<Environment>>> {
+        match self {
+            Captured::Strong(env) => Some(Rc::clone(env)),
+            Captured::Weak(env) => env.upgrade(),
+        }
+    }
+
+    pub fn as_ptr(&self) -> *const RefCell<Environment> {
+        match self {
+            Captured::Strong(env) => Rc::as_ptr(env),
+            Captured::Weak(env) => env.as_ptr(),
+        }
+    }
+}
+
+impl fmt::Debug for Closure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Closure")
+            .field("name", &self.name)
+            .field("params", &self.params)
+            .field("body", &self.body)
+            .field(
+                "env",
+                &format_args!("<environment at {:p}>", self.env.as_ptr()),
+            )
+            .finish()
+    }
+}
+
+impl PartialEq for Closure {
+    fn eq(&self, other: &Self) -> bool {
+        self.env.as_ptr() == other.env.as_ptr()
+            && self.name == other.name
+            && self.params == other.params
+            && self.body == other.body
+    }
+}
+
+impl Eq for Closure {}
+
+/// A function written in Rust, which receives the values of its arguments.
+pub type BuiltinFn = dyn Fn(&[Value]) -> Result<Value, ErrorKind>;
+
+// NOTE: `Debug` and `PartialEq` are written by hand, since functions can be neither printed
+// nor compared. Builtins are compared by identity.
+#[derive(Clone)]
+pub struct Builtin {
+    pub name: String,
+    pub arity: usize,
+    pub function: Rc<BuiltinFn>,
+}
+
+impl fmt::Debug for Builtin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Builtin")
+            .field("name", &self.name)
+            .field("arity", &self.arity)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for Builtin {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.function, &other.function)
+            && self.name == other.name
+            && self.arity == other.arity
+    }
+}
+
+impl Eq for Builtin {}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum SExpression {
     Symbol(String),
+    Integer(Integer),
+    String(String),
     Pair(Box<SExpression>, Box<SExpression>),
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+/// A range of the source text, in bytes, and the line and column where it starts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Span {
+    /// The span from the start of `self` to the end of `other`.
+    pub fn to(self, other: Span) -> Span {
+        Span {
+            end: other.end,
+            ..self
+        }
+    }
+}
+
+// NOTE: `PartialEq` is written by hand, so that terms are compared regardless of their spans.
+#[derive(Clone, Debug)]
 pub enum Term {
     /* Conditional Expressions */
     Cond(Vec<(Box<Term>, Box<Term>)>),
+    And(Vec<Box<Term>>),
+    Or(Vec<Box<Term>>),
+    Not(Box<Term>),
+    If(Box<Term>, Box<Term>, Option<Box<Term>>),
     /* The Elementary S-functions and Predicates */
-    Atom(Box<Term>),
-    Eq(Box<Term>, Box<Term>),
-    Car(Box<Term>),
-    Cdr(Box<Term>),
-    Cons(Box<Term>, Box<Term>),
+    /// A primitive written as a form, such as `(car x)`.
+    Primitive(&'static Primitive, Vec<Box<Term>>),
     /* Functions and Forms */
     Variable(String),
     Lambda(Vec<String>, Box<Term>),
     Apply(Box<Term>, Vec<Box<Term>>),
     /* Expressions for Recursive Functions */
     Label(String, Box<Term>),
+    Labels(Vec<(String, Box<Term>)>, Box<Term>),
     /* M-expression */
     Quote(SExpression),
+    /* Debugging */
+    Trace(String),
+    Untrace(String),
+    /* Macros */
+    Quasiquote(Box<Term>),
+    Unquote(Box<Term>),
+    UnquoteSplicing(Box<Term>),
+    Defmacro(String, Vec<String>, Box<Term>),
+    MacroCall(String, Vec<Box<Term>>),
+    /* Source Locations */
+    /// A term together with where the parser found it.
+    Spanned(Span, Box<Term>),
+}
+
+impl PartialEq for Term {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.node(), other.node()) {
+            (Term::Cond(clauses1), Term::Cond(clauses2)) => clauses1 == clauses2,
+            (Term::And(terms1), Term::And(terms2)) => terms1 == terms2,
+            (Term::Or(terms1), Term::Or(terms2)) => terms1 == terms2,
+            (Term::Not(term1), Term::Not(term2)) => term1 == term2,
+            (Term::If(term1, term2, term3), Term::If(term4, term5, term6)) => {
+                term1 == term4 && term2 == term5 && term3 == term6
+            }
+            (Term::Primitive(primitive1, terms1), Term::Primitive(primitive2, terms2)) => {
+                primitive1 == primitive2 && terms1 == terms2
+            }
+            (Term::Variable(name1), Term::Variable(name2)) => name1 == name2,
+            (Term::Lambda(params1, body1), Term::Lambda(params2, body2)) => {
+                params1 == params2 && body1 == body2
+            }
+            (Term::Apply(term1, terms1), Term::Apply(term2, terms2)) => {
+                term1 == term2 && terms1 == terms2
+            }
+            (Term::Label(name1, term1), Term::Label(name2, term2)) => {
+                name1 == name2 && term1 == term2
+            }
+            (Term::Labels(bindings1, term1), Term::Labels(bindings2, term2)) => {
+                bindings1 == bindings2 && term1 == term2
+            }
+            (Term::Quote(sexp1), Term::Quote(sexp2)) => sexp1 == sexp2,
+            (Term::Trace(name1), Term::Trace(name2)) => name1 == name2,
+            (Term::Untrace(name1), Term::Untrace(name2)) => name1 == name2,
+            (Term::Quasiquote(term1), Term::Quasiquote(term2)) => term1 == term2,
+            (Term::Unquote(term1), Term::Unquote(term2)) => term1 == term2,
+            (Term::UnquoteSplicing(term1), Term::UnquoteSplicing(term2)) => term1 == term2,
+            (Term::Defmacro(name1, params1, body1), Term::Defmacro(name2, params2, body2)) => {
+                name1 == name2 && params1 == params2 && body1 == body2
+            }
+            (Term::MacroCall(name1, terms1), Term::MacroCall(name2, terms2)) => {
+                name1 == name2 && terms1 == terms2
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Term {}
+
+impl Term {
+    /// The term without the spans around it.
+    pub fn node(&self) -> &Term {
+        match self {
+            Term::Spanned(_, term) => term.node(),
+            term => term,
+        }
+    }
+
+    /// Takes the term out of the spans around it.
+    pub fn into_node(self) -> Term {
+        match self {
+            Term::Spanned(_, term) => term.into_node(),
+            term => term,
+        }
+    }
+
+    /// Where the parser found the term, if it did.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            Term::Spanned(span, _) => Some(*span),
+            _ => None,
+        }
+    }
+
+    /// The direct subterms of the term, in the order they are written.
+    pub fn children(&self) -> Vec<&Term> {
+        match self {
+            Term::Cond(clauses) => clauses
+                .iter()
+                .flat_map(|(term1, term2)| [term1.as_ref(), term2.as_ref()])
+                .collect(),
+            Term::And(terms)
+            | Term::Or(terms)
+            | Term::Primitive(_, terms)
+            | Term::MacroCall(_, terms) => terms.iter().map(AsRef::as_ref).collect(),
+            Term::If(term1, term2, term3) => {
+                let mut children = vec![term1.as_ref(), term2.as_ref()];
+                children.extend(term3.as_deref());
+                children
+            }
+            Term::Not(term)
+            | Term::Lambda(_, term)
+            | Term::Label(_, term)
+            | Term::Quasiquote(term)
+            | Term::Unquote(term)
+            | Term::UnquoteSplicing(term)
+            | Term::Defmacro(_, _, term)
+            | Term::Spanned(_, term) => vec![term],
+            Term::Apply(term, terms) => {
+                let mut children = vec![term.as_ref()];
+                children.extend(terms.iter().map(AsRef::as_ref));
+                children
+            }
+            Term::Labels(bindings, term) => {
+                let mut children = bindings
+                    .iter()
+                    .map(|(_, term)| term.as_ref())
+                    .collect::<Vec<_>>();
+                children.push(term);
+                children
+            }
+            Term::Variable(_) | Term::Quote(_) | Term::Trace(_) | Term::Untrace(_) => vec![],
+        }
+    }
+
+    /// Rebuilds the term with `f` applied to each of its direct subterms.
+    pub fn try_map_children<E, F>(self, f: &mut F) -> Result<Term, E>
+    where
+        F: FnMut(Term) -> Result<Term, E>,
+    {
+        let mut g = |term: Box<Term>| f(*term).map(Box::new);
+
+        Ok(match self {
+            Term::Cond(clauses) => Term::Cond(
+                clauses
+                    .into_iter()
+                    .map(|(term1, term2)| Ok((g(term1)?, g(term2)?)))
+                    .collect::<Result<_, _>>()?,
+            ),
+            Term::And(terms) => Term::And(terms.into_iter().map(&mut g).collect::<Result<_, _>>()?),
+            Term::Or(terms) => Term::Or(terms.into_iter().map(&mut g).collect::<Result<_, _>>()?),
+            Term::Not(term) => Term::Not(g(term)?),
+            Term::If(term1, term2, term3) => Term::If(
+                g(term1)?,
+                g(term2)?,
+                match term3 {
+                    Some(term3) => Some(g(term3)?),
+                    None => None,
+                },
+            ),
+            Term::Primitive(primitive, terms) => Term::Primitive(
+                primitive,
+                terms.into_iter().map(&mut g).collect::<Result<_, _>>()?,
+            ),
+            Term::Variable(name) => Term::Variable(name),
+            Term::Lambda(params, body) => Term::Lambda(params, g(body)?),
+            Term::Apply(term, terms) => Term::Apply(
+                g(term)?,
+                terms.into_iter().map(&mut g).collect::<Result<_, _>>()?,
+            ),
+            Term::Label(name, term) => Term::Label(name, g(term)?),
+            Term::Labels(bindings, term) => Term::Labels(
+                bindings
+                    .into_iter()
+                    .map(|(name, term)| Ok((name, g(term)?)))
+                    .collect::<Result<_, _>>()?,
+                g(term)?,
+            ),
+            Term::Quote(sexp) => Term::Quote(sexp),
+            Term::Trace(name) => Term::Trace(name),
+            Term::Untrace(name) => Term::Untrace(name),
+            Term::Quasiquote(term) => Term::Quasiquote(g(term)?),
+            Term::Unquote(term) => Term::Unquote(g(term)?),
+            Term::UnquoteSplicing(term) => Term::UnquoteSplicing(g(term)?),
+            Term::Defmacro(name, params, body) => Term::Defmacro(name, params, g(body)?),
+            Term::MacroCall(name, terms) => Term::MacroCall(
+                name,
+                terms.into_iter().map(&mut g).collect::<Result<_, _>>()?,
+            ),
+            Term::Spanned(span, term) => Term::Spanned(span, g(term)?),
+        })
+    }
+}
+
+/// Something made of values or terms, whose symbols can be written to read back in a case mode.
+pub trait Print {
+    fn fmt_with(&self, case_mode: CaseMode, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+}
+
+/// Writes a value, a term or anything made of them, such as an error, so that its symbols
+/// read back in a case mode.
+///
+/// `Display` writes them for `CaseMode::Preserve`.
+pub struct Printed<'a, T: ?Sized>(pub CaseMode, pub &'a T);
+
+impl<T: Print + ?Sized> fmt::Display for Printed<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.1.fmt_with(self.0, f)
+    }
+}
+
+/// Writes the name of a symbol, between bars if the lexer would not read it back otherwise.
+fn write_symbol(f: &mut fmt::Formatter<'_>, name: &str, case_mode: CaseMode) -> fmt::Result {
+    if lexer::is_plain_symbol(name, case_mode) {
+        return write!(f, "{}", case_mode.unfold(name));
+    }
+    write!(f, "|")?;
+    for c in name.chars() {
+        match c {
+            '|' | '\\' => write!(f, "\\{}", c)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "|")
+}
+
+/// Writes a string in double quotes, with the escapes that the lexer reads back.
+fn write_string(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\t' => write!(f, "\\t")?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+impl Print for Value {
+    fn fmt_with(&self, case_mode: CaseMode, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Symbol(s) => write_symbol(f, s, case_mode),
+            Value::Integer(n) => write!(f, "{}", n),
+            Value::String(s) => write_string(f, s),
+            Value::Pair(value1, value2) => write!(
+                f,
+                "({} {})",
+                Printed(case_mode, &**value1),
+                Printed(case_mode, &**value2)
+            ),
+            Value::Closure(_) => write!(f, "<closure>"),
+            Value::Builtin(builtin) => write!(f, "<builtin {}>", builtin.name),
+            Value::Code(term) => term.fmt_with(case_mode, f),
+            Value::Cell(index) => write!(f, "<cell {}>", index),
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with(CaseMode::Preserve, f)
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+impl Print for SExpression {
+    fn fmt_with(&self, case_mode: CaseMode, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SExpression::Symbol(s) => write_symbol(f, s, case_mode),
+            SExpression::Integer(n) => write!(f, "{}", n),
+            SExpression::String(s) => write_string(f, s),
+            SExpression::Pair(sexp1, sexp2) => write!(
+                f,
+                "({} {})",
+                Printed(case_mode, &**sexp1),
+                Printed(case_mode, &**sexp2)
+            ),
+        }
+    }
+}
+
+impl fmt::Display for SExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with(CaseMode::Preserve, f)
+    }
+}
+
+/// A name bound by a term, which is written between bars if it is a keyword.
+struct Name<'a>(CaseMode, &'a str);
+
+impl fmt::Display for Name<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if lexer::is_keyword(self.1, self.0) {
+            write!(f, "|{}|", self.1)
+        } else {
+            write_symbol(f, self.1, self.0)
+        }
+    }
+}
+
+impl Print for Term {
+    fn fmt_with(&self, case_mode: CaseMode, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn join<T: fmt::Display>(items: impl Iterator<Item = T>) -> String {
+            items
+                .map(|item| item.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        }
+
+        let printed = |term: &Term| Printed(case_mode, term).to_string();
+        let name = |name| Name(case_mode, name);
+        match self {
+            Term::Cond(clauses) => write!(
+                f,
+                "(cond{}{})",
+                if clauses.is_empty() { "" } else { " " },
+                join(clauses.iter().map(|(term1, term2)| format!(
+                    "({} {})",
+                    printed(term1),
+                    printed(term2)
+                )))
+            ),
+            Term::And(terms) if terms.is_empty() => write!(f, "(and)"),
+            Term::And(terms) => write!(f, "(and {})", join(terms.iter().map(|term| printed(term)))),
+            Term::Or(terms) if terms.is_empty() => write!(f, "(or)"),
+            Term::Or(terms) => write!(f, "(or {})", join(terms.iter().map(|term| printed(term)))),
+            Term::Not(term) => write!(f, "(not {})", printed(term)),
+            Term::If(term1, term2, Some(term3)) => {
+                write!(
+                    f,
+                    "(if {} {} {})",
+                    printed(term1),
+                    printed(term2),
+                    printed(term3)
+                )
+            }
+            Term::If(term1, term2, None) => {
+                write!(f, "(if {} {})", printed(term1), printed(term2))
+            }
+            Term::Primitive(primitive, terms) if terms.is_empty() => {
+                write!(f, "({})", primitive.name)
+            }
+            Term::Primitive(primitive, terms) => write!(
+                f,
+                "({} {})",
+                primitive.name,
+                join(terms.iter().map(|term| printed(term)))
+            ),
+            Term::Variable(variable) => write!(f, "{}", name(variable)),
+            Term::Lambda(params, body) => write!(
+                f,
+                "(lambda ({}) {})",
+                join(params.iter().map(|param| name(param))),
+                printed(body)
+            ),
+            Term::Apply(term, terms) => write!(
+                f,
+                "(apply {} ({}))",
+                printed(term),
+                join(terms.iter().map(|term| match term.as_ref() {
+                    // NOTE: A spliced argument takes the place of the separating comma.
+                    Term::UnquoteSplicing(_) => printed(term),
+                    _ => format!(",{}", printed(term)),
+                }))
+            ),
+            Term::Label(label, term) => write!(f, "(label {} {})", name(label), printed(term)),
+            Term::Labels(bindings, term) => write!(
+                f,
+                "(labels ({}) {})",
+                join(bindings.iter().map(|(label, term)| format!(
+                    "({} {})",
+                    name(label),
+                    printed(term)
+                ))),
+                printed(term)
+            ),
+            Term::Quote(sexp) => write!(f, "'{}", Printed(case_mode, sexp)),
+            Term::Trace(traced) => write!(f, "(trace {})", name(traced)),
+            Term::Untrace(traced) => write!(f, "(untrace {})", name(traced)),
+            Term::Quasiquote(term) => write!(f, "`{}", printed(term)),
+            Term::Unquote(term) => write!(f, ",{}", printed(term)),
+            Term::UnquoteSplicing(term) => write!(f, ",@{}", printed(term)),
+            Term::Defmacro(macro_name, params, body) => {
+                write!(
+                    f,
+                    "(defmacro {} ({}) {})",
+                    name(macro_name),
+                    join(params.iter().map(|param| name(param))),
+                    printed(body)
+                )
+            }
+            Term::MacroCall(macro_name, terms) => {
+                if terms.is_empty() {
+                    write!(f, "({})", name(macro_name))
+                } else {
+                    write!(
+                        f,
+                        "({} {})",
+                        name(macro_name),
+                        join(terms.iter().map(|term| printed(term)))
+                    )
+                }
+            }
+            Term::Spanned(_, term) => term.fmt_with(case_mode, f),
+        }
+    }
+}
+
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with(CaseMode::Preserve, f)
+    }
 }
@@ -0,0 +1,285 @@
+use crate::{
+    primitive,
+    syntax::{SExpression, Span, Term},
+};
+
+/// Whether a function defined by `label` or `labels` is proven to terminate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Verdict {
+    pub name: String,
+    /// Where the function is written, if it was parsed.
+    pub span: Option<Span>,
+    pub proven: bool,
+}
+
+/// Tries to prove that each function defined by `label` or `labels` in `term` terminates.
+///
+/// A function terminates when one of its parameters gets smaller in every recursive call,
+/// i.e. the argument is a `car` or `cdr` of the parameter where an `atom` or `null` test has
+/// found that the parameter is not an atom.
+/// The functions of one `labels` must all recurse on the parameter at the same position.
+pub fn check(term: &Term) -> Vec<Verdict> {
+    let mut verdicts = vec![];
+    find(term, None, &mut verdicts);
+    verdicts
+}
+
+fn find(term: &Term, span: Option<Span>, verdicts: &mut Vec<Verdict>) {
+    match term {
+        Term::Spanned(span, term) => return find(term, Some(*span), verdicts),
+        Term::Label(name, term) => verdicts.extend(prove(&[(name, term, span)])),
+        Term::Labels(bindings, _) => {
+            let bindings = bindings
+                .iter()
+                .map(|(name, term)| (name, term.as_ref(), term.span().or(span)))
+                .collect::<Vec<_>>();
+            verdicts.extend(prove(&bindings));
+        }
+        _ => (),
+    }
+    for term in term.children() {
+        find(term, span, verdicts);
+    }
+}
+
+/// Tries to prove that the functions bound together by `label` or `labels` terminate.
+/// Each of them is given with where it is written.
+fn prove(bindings: &[(&String, &Term, Option<Span>)]) -> Vec<Verdict> {
+    let names = bindings
+        .iter()
+        .map(|(name, _, _)| name.as_str())
+        .collect::<Vec<_>>();
+    // NOTE: Each call is given by the caller, the callee and the arguments that get smaller.
+    let mut calls = vec![];
+    let mut arity = 0;
+    for (caller, (_, term, _)) in bindings.iter().enumerate() {
+        if let Term::Lambda(params, body) = term.node() {
+            let mut walker = Walker {
+                names: &names,
+                params,
+                shadowed: vec![],
+                guarded: vec![],
+                calls: vec![],
+            };
+            walker.walk(body);
+            calls.extend(
+                walker
+                    .calls
+                    .into_iter()
+                    .map(|(callee, smaller)| (caller, callee, smaller)),
+            );
+            arity = arity.max(params.len());
+        }
+    }
+
+    // NOTE: `reaches[i][j]` tells whether the function `i` may call the function `j` on the way.
+    let len = bindings.len();
+    let mut reaches = vec![vec![false; len]; len];
+    for (caller, callee, _) in &calls {
+        reaches[*caller][*callee] = true;
+    }
+    for k in 0..len {
+        for i in 0..len {
+            for j in 0..len {
+                reaches[i][j] |= reaches[i][k] && reaches[k][j];
+            }
+        }
+    }
+
+    // NOTE: Only the calls that may come back to the caller repeat, and they must all pass
+    // a smaller argument at the same position.
+    let terminates = |function: usize| {
+        let cycle = |caller: usize, callee: usize| {
+            reaches[callee][caller] && reaches[caller][function] && reaches[function][caller]
+        };
+        (0..arity).any(|index| {
+            calls
+                .iter()
+                .filter(|(caller, callee, _)| cycle(*caller, *callee))
+                .all(|(_, _, smaller)| smaller.contains(&index))
+        }) || !calls
+            .iter()
+            .any(|(caller, callee, _)| cycle(*caller, *callee))
+    };
+    bindings
+        .iter()
+        .enumerate()
+        .map(|(function, (name, _, span))| Verdict {
+            name: name.to_string(),
+            span: *span,
+            proven: (0..len)
+                .filter(|other| *other == function || reaches[function][*other])
+                .all(terminates),
+        })
+        .collect()
+}
+
+/// Finds the recursive calls in the body of a function.
+struct Walker<'a> {
+    /// The functions defined together.
+    names: &'a [&'a str],
+    params: &'a [String],
+    /// The variables bound again around the current term, which hide the functions and the
+    /// parameters.
+    shadowed: Vec<String>,
+    /// The parameters that are known to be pairs in the current term.
+    guarded: Vec<String>,
+    /// The callee of each recursive call, with the positions of the arguments that get smaller.
+    calls: Vec<(usize, Vec<usize>)>,
+}
+
+impl Walker<'_> {
+    fn walk(&mut self, term: &Term) {
+        match term.node() {
+            Term::Cond(clauses) => {
+                let len = self.guarded.len();
+                for (term1, term2) in clauses {
+                    self.walk(term1);
+                    let outer = self.guarded.len();
+                    self.guard(term1, true);
+                    self.walk(term2);
+                    self.guarded.truncate(outer);
+                    // NOTE: The later clauses are reached only if the predicate is false.
+                    self.guard(term1, false);
+                }
+                self.guarded.truncate(len);
+            }
+            Term::And(terms) | Term::Or(terms) => {
+                let and = matches!(term.node(), Term::And(_));
+                let len = self.guarded.len();
+                for term in terms {
+                    self.walk(term);
+                    self.guard(term, and);
+                }
+                self.guarded.truncate(len);
+            }
+            Term::If(term1, term2, term3) => {
+                self.walk(term1);
+                let len = self.guarded.len();
+                self.guard(term1, true);
+                self.walk(term2);
+                self.guarded.truncate(len);
+                if let Some(term3) = term3 {
+                    self.guard(term1, false);
+                    self.walk(term3);
+                    self.guarded.truncate(len);
+                }
+            }
+            Term::Apply(callee, args) => {
+                match self.function(callee) {
+                    Some(function) => {
+                        let smaller = args
+                            .iter()
+                            .enumerate()
+                            .filter(|(index, arg)| self.decreases(*index, arg))
+                            .map(|(index, _)| index)
+                            .collect();
+                        self.calls.push((function, smaller));
+                    }
+                    None => self.walk(callee),
+                }
+                for arg in args {
+                    self.walk(arg);
+                }
+            }
+            Term::Variable(_) => {
+                // NOTE: A function that is passed around may be called with anything.
+                if let Some(function) = self.function(term) {
+                    self.calls.push((function, vec![]));
+                }
+            }
+            Term::Lambda(params, body) => self.walk_shadowed(params.iter(), body),
+            Term::Label(name, body) => self.walk_shadowed([name], body),
+            Term::Labels(bindings, _) => {
+                let len = self.shadowed.len();
+                self.shadowed
+                    .extend(bindings.iter().map(|(name, _)| name.clone()));
+                for term in term.children() {
+                    self.walk(term);
+                }
+                self.shadowed.truncate(len);
+            }
+            Term::Defmacro(_, _, _) => (),
+            term => {
+                for term in term.children() {
+                    self.walk(term);
+                }
+            }
+        }
+    }
+
+    fn walk_shadowed<'b>(&mut self, names: impl IntoIterator<Item = &'b String>, body: &Term) {
+        let len = self.shadowed.len();
+        self.shadowed.extend(names.into_iter().cloned());
+        self.walk(body);
+        self.shadowed.truncate(len);
+    }
+
+    /// The index of the function that `term` names, if it is one of those defined together.
+    fn function(&self, term: &Term) -> Option<usize> {
+        match term.node() {
+            Term::Variable(name) if !self.shadowed.contains(name) => {
+                self.names.iter().position(|function| function == name)
+            }
+            _ => None,
+        }
+    }
+
+    fn param<'t>(&self, term: &'t Term) -> Option<&'t String> {
+        match term.node() {
+            Term::Variable(name) if self.params.contains(name) && !self.shadowed.contains(name) => {
+                Some(name)
+            }
+            _ => None,
+        }
+    }
+
+    /// Records the parameters that are pairs if `term` evaluates to `T` (or `F` if not `positive`).
+    fn guard(&mut self, term: &Term, positive: bool) {
+        let param = match term.node() {
+            Term::Primitive(primitive, terms) if !positive => {
+                let nil = Term::Quote(SExpression::Symbol("NIL".to_string()));
+                match (primitive.name, terms.as_slice()) {
+                    ("atom", [term]) => self.param(term),
+                    ("eq", [term1, term2]) if **term2 == nil => self.param(term1),
+                    ("eq", [term1, term2]) if **term1 == nil => self.param(term2),
+                    _ => None,
+                }
+            }
+            Term::Apply(callee, args) if !positive && args.len() == 1 => match callee.node() {
+                Term::Variable(name) if name == "null" => self.param(&args[0]),
+                _ => None,
+            },
+            Term::Not(term) => return self.guard(term, !positive),
+            Term::And(terms) | Term::Or(terms) => {
+                if matches!(term.node(), Term::And(_)) == positive {
+                    for term in terms {
+                        self.guard(term, positive);
+                    }
+                }
+                return;
+            }
+            _ => None,
+        };
+        if let Some(param) = param.cloned() {
+            self.guarded.push(param);
+        }
+    }
+
+    /// Whether `arg` is a `car`, `cdr` or `c[ad]+r` of the guarded parameter at `index`.
+    fn decreases(&self, index: usize, arg: &Term) -> bool {
+        let mut term = arg.node();
+        let mut smaller = false;
+        while let Term::Primitive(primitive, terms) = term {
+            match (primitive::accessor_path(primitive.name), terms.as_slice()) {
+                (Some(_), [inner]) => term = inner.node(),
+                _ => break,
+            }
+            smaller = true;
+        }
+        smaller
+            && self.param(term).is_some_and(|param| {
+                self.params.get(index) == Some(param) && self.guarded.contains(param)
+            })
+    }
+}
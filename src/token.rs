@@ -1,17 +1,26 @@
+use crate::integer::Integer;
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Token {
     Id(String),
+    Integer(Integer),
+    String(String),
     LParen,
     RParen,
     Comma,
+    CommaAt,
+    Backquote,
     Cond,
-    Atom,
-    Eq,
-    Car,
-    Cdr,
-    Cons,
+    And,
+    Or,
+    Not,
+    If,
     Lambda,
     Apply,
     Label,
+    Labels,
     Quote,
+    Trace,
+    Untrace,
+    Defmacro,
 }
@@ -0,0 +1,44 @@
+use std::io::Write;
+
+use crate::{
+    error::EvalError,
+    lexer::CaseMode,
+    syntax::{Printed, Value},
+};
+
+/// Receives the applications of traced functions.
+pub trait Tracer {
+    /// Called before the function `name` is applied to `args`, within `depth` traced applications.
+    fn enter(&mut self, depth: usize, name: &str, args: &[Value]);
+
+    /// Called after the function `name` has given `result`, within `depth` traced applications.
+    fn exit(&mut self, depth: usize, name: &str, result: &Result<Value, EvalError>);
+}
+
+/// Writes each application on its own line, indented by its depth,
+/// with its symbols written to read back in the case mode.
+pub struct PrintTracer<W: Write>(pub W, pub CaseMode);
+
+impl<W: Write> Tracer for PrintTracer<W> {
+    fn enter(&mut self, depth: usize, name: &str, args: &[Value]) {
+        let args = args
+            .iter()
+            .map(|arg| format!(" {}", Printed(self.1, arg)))
+            .collect::<String>();
+        // NOTE: Tracing must not disturb the evaluation, so write errors are ignored.
+        let _ = writeln!(self.0, "{}({}{})", "  ".repeat(depth), name, args);
+    }
+
+    fn exit(&mut self, depth: usize, name: &str, result: &Result<Value, EvalError>) {
+        let _ = match result {
+            Ok(value) => {
+                let value = Printed(self.1, value);
+                writeln!(self.0, "{}{} returned {}", "  ".repeat(depth), name, value)
+            }
+            Err(err) => {
+                let err = Printed(self.1, err);
+                writeln!(self.0, "{}{} failed: {}", "  ".repeat(depth), name, err)
+            }
+        };
+    }
+}
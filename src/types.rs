@@ -0,0 +1,678 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::{
+    environment::Environment,
+    error::{TypeError, TypeErrorKind},
+    primitive,
+    syntax::{SExpression, Span, Term, Value},
+};
+
+/// The type of a value, as far as it can be known without running the program.
+///
+/// A list is a pair whose second component is again a list, ending in any atom.
+/// So `List<a>` matches `Pair<a, List<a>>` and the atoms.
+/// A pair that contains itself otherwise is an `SExp`, e.g. the argument of `ff`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Type {
+    Var(usize),
+    Atom,
+    /// The atoms `T` and `F`, which also match `Atom`.
+    Bool,
+    /// The integers, which also match `Atom`.
+    Integer,
+    /// The strings, which also match `Atom`.
+    String,
+    /// The symbols, which also match `Atom` and `Bool`, but not `Integer` or `String`.
+    Symbol,
+    List(Box<Type>),
+    Pair(Box<Type>, Box<Type>),
+    /// Any atom, or any pair of S-expressions.
+    SExp,
+    Fn(Vec<Type>, Box<Type>),
+    /// A term made by a quasiquote.
+    Code,
+}
+
+impl Type {
+    fn vars(&self, vars: &mut Vec<usize>) {
+        match self {
+            Type::Var(var) => {
+                if !vars.contains(var) {
+                    vars.push(*var);
+                }
+            }
+            Type::Atom
+            | Type::Bool
+            | Type::Integer
+            | Type::String
+            | Type::Symbol
+            | Type::SExp
+            | Type::Code => (),
+            Type::List(ty) => ty.vars(vars),
+            Type::Pair(ty1, ty2) => {
+                ty1.vars(vars);
+                ty2.vars(vars);
+            }
+            Type::Fn(params, ty) => {
+                for param in params {
+                    param.vars(vars);
+                }
+                ty.vars(vars);
+            }
+        }
+    }
+
+    fn rename(&self, renames: &HashMap<usize, usize>) -> Type {
+        match self {
+            Type::Var(var) => Type::Var(renames[var]),
+            Type::List(ty) => Type::List(Box::new(ty.rename(renames))),
+            Type::Pair(ty1, ty2) => {
+                Type::Pair(Box::new(ty1.rename(renames)), Box::new(ty2.rename(renames)))
+            }
+            Type::Fn(params, ty) => Type::Fn(
+                params.iter().map(|param| param.rename(renames)).collect(),
+                Box::new(ty.rename(renames)),
+            ),
+            ty => ty.clone(),
+        }
+    }
+}
+
+/// Numbers the type variables of `types` from zero, in order of appearance.
+fn normalize(types: &[Type]) -> Vec<Type> {
+    let mut vars = vec![];
+    for ty in types {
+        ty.vars(&mut vars);
+    }
+    let renames = vars
+        .into_iter()
+        .enumerate()
+        .map(|(index, var)| (var, index))
+        .collect();
+    types.iter().map(|ty| ty.rename(&renames)).collect()
+}
+
+/// A type whose variables may be replaced by any types, e.g. of a function of the prelude.
+#[derive(Clone, Debug)]
+struct Scheme {
+    ty: Type,
+    /// Which variables only stand for atoms, by their numbers in `ty`.
+    atomic: HashSet<usize>,
+}
+
+/// Infers the types of terms before they are evaluated.
+pub struct TypeChecker {
+    globals: HashMap<String, Scheme>,
+}
+
+impl TypeChecker {
+    /// Creates a checker for terms evaluated in `env`.
+    pub fn new(env: &Environment) -> Self {
+        let mut checker = TypeChecker {
+            globals: HashMap::new(),
+        };
+
+        // NOTE: Functions are typed from their terms, in any order, so that they can use each other.
+        let mut terms = HashMap::new();
+        for (name, value) in env.iter() {
+            match value {
+                Value::Closure(closure) => {
+                    let lambda = Term::Lambda(closure.params.clone(), closure.body.clone());
+                    let term = match &closure.name {
+                        Some(label) => Term::Label(label.clone(), Box::new(lambda)),
+                        None => lambda,
+                    };
+                    terms.insert(name.clone(), term);
+                }
+                value => {
+                    if let Some(ty) = type_of_value(value) {
+                        checker.globals.insert(
+                            name.clone(),
+                            Scheme {
+                                ty,
+                                atomic: HashSet::new(),
+                            },
+                        );
+                    }
+                }
+            }
+        }
+        let names = terms.keys().cloned().collect::<Vec<_>>();
+        for name in names {
+            checker.define(&name, &mut terms);
+        }
+        checker
+    }
+
+    /// Types the global function `name`, after the functions it uses.
+    fn define(&mut self, name: &String, terms: &mut HashMap<String, Term>) {
+        let Some(term) = terms.remove(name) else {
+            return;
+        };
+        let mut vars = vec![];
+        free_variables(&term, &mut vec![], &mut vars);
+        for var in vars {
+            self.define(&var, terms);
+        }
+
+        // NOTE: A function that cannot be typed is left unbound.
+        if let Ok(scheme) = self.scheme(&term) {
+            self.globals.insert(name.clone(), scheme);
+        }
+    }
+
+    /// Infers the type of `term`, or tells why it has none.
+    pub fn infer(&self, term: &Term) -> Result<Type, TypeError> {
+        Ok(self.scheme(term)?.ty)
+    }
+
+    fn scheme(&self, term: &Term) -> Result<Scheme, TypeError> {
+        let mut inference = Inference::new(self);
+        let ty = inference.infer(term).map_err(|kind| TypeError {
+            kind,
+            span: inference.span,
+        })?;
+
+        let ty = inference.zonk(&ty);
+        let mut vars = vec![];
+        ty.vars(&mut vars);
+        Ok(Scheme {
+            ty: normalize(&[ty]).remove(0),
+            atomic: vars
+                .into_iter()
+                .enumerate()
+                .filter(|(_, var)| inference.atomic[*var])
+                .map(|(index, _)| index)
+                .collect(),
+        })
+    }
+}
+
+/// The variables used but not bound in `term`.
+fn free_variables(term: &Term, bound: &mut Vec<String>, vars: &mut Vec<String>) {
+    let len = bound.len();
+    match term {
+        Term::Variable(name) if !bound.contains(name) && !vars.contains(name) => {
+            vars.push(name.clone());
+        }
+        Term::Lambda(params, _) | Term::Defmacro(_, params, _) => {
+            bound.extend(params.iter().cloned());
+        }
+        Term::Label(name, _) => bound.push(name.clone()),
+        Term::Labels(bindings, _) => bound.extend(bindings.iter().map(|(name, _)| name.clone())),
+        _ => (),
+    }
+    for term in term.children() {
+        free_variables(term, bound, vars);
+    }
+    bound.truncate(len);
+}
+
+fn type_of_value(value: &Value) -> Option<Type> {
+    match value {
+        Value::Symbol(name) if name == "T" || name == "F" => Some(Type::Bool),
+        Value::Symbol(_) => Some(Type::Atom),
+        Value::Integer(_) => Some(Type::Integer),
+        Value::String(_) => Some(Type::String),
+        Value::Pair(value1, value2) => Some(Type::Pair(
+            Box::new(type_of_value(value1)?),
+            Box::new(type_of_value(value2)?),
+        )),
+        Value::Code(_) => Some(Type::Code),
+        // NOTE: Nothing is known about the arguments and the result of a builtin.
+        Value::Builtin(builtin) => Some(Type::Fn(
+            (0..builtin.arity).map(Type::Var).collect(),
+            Box::new(Type::Var(builtin.arity)),
+        )),
+        Value::Closure(_) | Value::Cell(_) => None,
+    }
+}
+
+/// The state of inferring the type of a single term.
+struct Inference<'a> {
+    checker: &'a TypeChecker,
+    /// What each type variable stands for, once it is known.
+    vars: Vec<Option<Type>>,
+    /// Whether each type variable only stands for atoms, as the arguments of `eq`.
+    atomic: Vec<bool>,
+    /// The types of the variables bound around the current term, innermost last.
+    scope: Vec<(String, Type)>,
+    /// Where the current term is written, if it was parsed.
+    span: Option<Span>,
+}
+
+impl<'a> Inference<'a> {
+    fn new(checker: &'a TypeChecker) -> Self {
+        Inference {
+            checker,
+            vars: vec![],
+            atomic: vec![],
+            scope: vec![],
+            span: None,
+        }
+    }
+
+    fn fresh(&mut self) -> Type {
+        self.vars.push(None);
+        self.atomic.push(false);
+        Type::Var(self.vars.len() - 1)
+    }
+
+    fn instantiate(&mut self, ty: &Type, atomic: &HashSet<usize>) -> Type {
+        let mut vars = vec![];
+        ty.vars(&mut vars);
+        let renames = vars
+            .into_iter()
+            .map(|var| {
+                let Type::Var(fresh) = self.fresh() else {
+                    unreachable!()
+                };
+                self.atomic[fresh] = atomic.contains(&var);
+                (var, fresh)
+            })
+            .collect();
+        ty.rename(&renames)
+    }
+
+    /// Follows the known type variables at the top of `ty`.
+    fn resolve(&self, ty: &Type) -> Type {
+        match ty {
+            Type::Var(var) => match &self.vars[*var] {
+                Some(ty) => self.resolve(ty),
+                None => ty.clone(),
+            },
+            ty => ty.clone(),
+        }
+    }
+
+    /// The variable that `ty` names through other variables, if it names one.
+    fn last_var(&self, ty: &Type) -> Option<usize> {
+        match ty {
+            Type::Var(var) => match &self.vars[*var] {
+                Some(ty @ Type::Var(_)) => self.last_var(ty),
+                _ => Some(*var),
+            },
+            _ => None,
+        }
+    }
+
+    /// Replaces all the known type variables in `ty`.
+    fn zonk(&self, ty: &Type) -> Type {
+        match self.resolve(ty) {
+            Type::List(ty) => match self.zonk(&ty) {
+                Type::SExp => Type::SExp,
+                ty => Type::List(Box::new(ty)),
+            },
+            Type::Pair(ty1, ty2) => match (self.zonk(&ty1), self.zonk(&ty2)) {
+                // NOTE: A tree that is taken apart may also be an atom, where its walk ends,
+                // and a list of trees is a tree itself.
+                (Type::SExp, Type::SExp) => Type::SExp,
+                (ty1, ty2) => Type::Pair(Box::new(ty1), Box::new(ty2)),
+            },
+            Type::Fn(params, ty) => Type::Fn(
+                params.iter().map(|param| self.zonk(param)).collect(),
+                Box::new(self.zonk(&ty)),
+            ),
+            ty => ty,
+        }
+    }
+
+    fn occurs(&self, var: usize, ty: &Type) -> bool {
+        let mut vars = vec![];
+        self.zonk(ty).vars(&mut vars);
+        vars.contains(&var)
+    }
+
+    fn mismatch(&self, ty1: &Type, ty2: &Type) -> TypeErrorKind {
+        let mut types = normalize(&[self.zonk(ty1), self.zonk(ty2)]);
+        let ty2 = types.pop().unwrap();
+        TypeErrorKind::Mismatch(types.pop().unwrap(), ty2)
+    }
+
+    fn unify(&mut self, ty1: &Type, ty2: &Type) -> Result<(), TypeErrorKind> {
+        self.unify_resolved(self.resolve(ty1), self.resolve(ty2))?;
+        // NOTE: A variable that was taken for a pair before it was found to be a list, as the
+        // result of a recursive function, is widened to the list.
+        let others = [self.resolve(ty2), self.resolve(ty1)];
+        for (var, other) in [self.last_var(ty1), self.last_var(ty2)]
+            .into_iter()
+            .zip(others)
+        {
+            if let (Some(var), Type::List(_) | Type::SExp) = (var, &other) {
+                if let Some(Type::Pair(_, _)) = self.vars[var] {
+                    self.vars[var] = Some(other);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn unify_resolved(&mut self, ty1: Type, ty2: Type) -> Result<(), TypeErrorKind> {
+        match (ty1, ty2) {
+            (Type::Var(var1), Type::Var(var2)) if var1 == var2 => Ok(()),
+            (Type::Var(var), ty) | (ty, Type::Var(var)) => self.bind(var, ty),
+            (Type::Atom | Type::Bool, Type::Atom | Type::Bool) | (Type::Code, Type::Code) => Ok(()),
+            (Type::Integer, Type::Integer | Type::Atom) | (Type::Atom, Type::Integer) => Ok(()),
+            (Type::String, Type::String | Type::Atom) | (Type::Atom, Type::String) => Ok(()),
+            (Type::Symbol, Type::Symbol | Type::Atom | Type::Bool)
+            | (Type::Atom | Type::Bool, Type::Symbol) => Ok(()),
+            (Type::List(_), Type::Atom | Type::Bool | Type::Symbol)
+            | (Type::Atom | Type::Bool | Type::Symbol, Type::List(_)) => Ok(()),
+            (
+                Type::SExp,
+                Type::SExp | Type::Atom | Type::Bool | Type::Integer | Type::String | Type::Symbol,
+            )
+            | (Type::Atom | Type::Bool | Type::Integer | Type::String | Type::Symbol, Type::SExp) => {
+                Ok(())
+            }
+            (Type::SExp, Type::List(ty)) | (Type::List(ty), Type::SExp) => {
+                self.unify(&Type::SExp, &ty)
+            }
+            (Type::SExp, Type::Pair(ty1, ty2)) | (Type::Pair(ty1, ty2), Type::SExp) => {
+                self.unify(&Type::SExp, &ty1)?;
+                self.unify(&Type::SExp, &ty2)
+            }
+            (Type::List(ty1), Type::List(ty2)) => self.unify(&ty1, &ty2),
+            (Type::List(ty), Type::Pair(ty1, ty2)) | (Type::Pair(ty1, ty2), Type::List(ty)) => {
+                self.unify(&ty, &ty1)?;
+                self.unify(&Type::List(ty), &ty2)
+            }
+            (Type::Pair(ty1, ty2), Type::Pair(ty3, ty4)) => {
+                self.unify(&ty1, &ty3)?;
+                self.unify(&ty2, &ty4)
+            }
+            (Type::Fn(params1, ty1), Type::Fn(params2, ty2)) if params1.len() == params2.len() => {
+                for (param1, param2) in params1.iter().zip(&params2) {
+                    self.unify(param1, param2)?;
+                }
+                self.unify(&ty1, &ty2)
+            }
+            (ty1, ty2) => Err(self.mismatch(&ty1, &ty2)),
+        }
+    }
+
+    fn bind(&mut self, var: usize, ty: Type) -> Result<(), TypeErrorKind> {
+        // NOTE: A pair that contains itself in its second component, or that is compared by
+        // `eq`, is taken as a list. One that contains itself elsewhere is any S-expression.
+        let recursive = match &ty {
+            Type::Pair(ty1, _) | Type::List(ty1) if self.occurs(var, ty1) => Some(Type::SExp),
+            Type::Pair(ty1, _) if self.atomic[var] || self.occurs(var, &ty) => {
+                Some(Type::List(ty1.clone()))
+            }
+            _ => None,
+        };
+        if let Some(recursive) = recursive {
+            self.vars[var] = Some(recursive.clone());
+            return self.unify(&recursive, &ty);
+        }
+
+        match &ty {
+            Type::Var(other) if self.atomic[var] => self.atomic[*other] = true,
+            Type::Fn(_, _) | Type::Code if self.atomic[var] => {
+                return Err(TypeErrorKind::Mismatch(
+                    Type::Atom,
+                    normalize(&[ty]).remove(0),
+                ))
+            }
+            _ if self.occurs(var, &ty) => {
+                return Err(TypeErrorKind::Infinite(normalize(&[ty]).remove(0)))
+            }
+            _ => (),
+        }
+        self.vars[var] = Some(ty);
+        Ok(())
+    }
+
+    fn infer(&mut self, term: &Term) -> Result<Type, TypeErrorKind> {
+        match term {
+            Term::Spanned(span, term) => {
+                let outer = self.span.replace(*span);
+                let ty = self.infer(term)?;
+                // NOTE: The span is only restored without errors, so that an error points at its term.
+                self.span = outer;
+                Ok(ty)
+            }
+            /* Conditional Expressions */
+            Term::Cond(clauses) => {
+                let ty = self.fresh();
+                for (term1, term2) in clauses {
+                    self.check(term1, &Type::Bool)?;
+                    self.check(term2, &ty)?;
+                }
+                Ok(ty)
+            }
+            Term::And(terms) | Term::Or(terms) => {
+                for term in terms {
+                    self.check(term, &Type::Bool)?;
+                }
+                Ok(Type::Bool)
+            }
+            Term::Not(term) => {
+                self.check(term, &Type::Bool)?;
+                Ok(Type::Bool)
+            }
+            Term::If(term1, term2, term3) => {
+                self.check(term1, &Type::Bool)?;
+                let ty = self.infer(term2)?;
+                if let Some(term3) = term3 {
+                    self.check(term3, &ty)?;
+                }
+                Ok(ty)
+            }
+            /* The Elementary S-functions and Predicates */
+            Term::Primitive(primitive, terms) => {
+                if let (Some(path), [term]) =
+                    (primitive::accessor_path(primitive.name), terms.as_slice())
+                {
+                    return self.infer_access(path, term);
+                }
+                match (primitive.name, terms.as_slice()) {
+                    ("atom", [term]) => {
+                        self.infer(term)?;
+                        Ok(Type::Bool)
+                    }
+                    ("eq", [term1, term2]) => {
+                        for term in [term1, term2] {
+                            let ty = self.infer(term)?;
+                            if let Type::Pair(_, _) = self.resolve(&ty) {
+                                return Err(self.mismatch(&Type::Atom, &ty));
+                            }
+                            let atom = self.fresh();
+                            if let Type::Var(var) = atom {
+                                self.atomic[var] = true;
+                            }
+                            self.unify(&atom, &ty)?;
+                        }
+                        Ok(Type::Bool)
+                    }
+                    ("cons", [term1, term2]) => Ok(Type::Pair(
+                        Box::new(self.infer(term1)?),
+                        Box::new(self.infer(term2)?),
+                    )),
+                    ("+" | "-" | "*" | "quotient" | "remainder", [term1, term2]) => {
+                        self.check(term1, &Type::Integer)?;
+                        self.check(term2, &Type::Integer)?;
+                        Ok(Type::Integer)
+                    }
+                    ("<" | "=" | ">", [term1, term2]) => {
+                        self.check(term1, &Type::Integer)?;
+                        self.check(term2, &Type::Integer)?;
+                        Ok(Type::Bool)
+                    }
+                    ("string-append", [term1, term2]) => {
+                        self.check(term1, &Type::String)?;
+                        self.check(term2, &Type::String)?;
+                        Ok(Type::String)
+                    }
+                    ("symbol->string", [term]) => {
+                        self.check(term, &Type::Symbol)?;
+                        Ok(Type::String)
+                    }
+                    ("string->symbol", [term]) => {
+                        self.check(term, &Type::String)?;
+                        Ok(Type::Symbol)
+                    }
+                    ("explode", [term]) => {
+                        self.check(term, &Type::Symbol)?;
+                        Ok(Type::List(Box::new(Type::Symbol)))
+                    }
+                    ("implode", [term]) => {
+                        self.check(term, &Type::List(Box::new(Type::Symbol)))?;
+                        Ok(Type::Symbol)
+                    }
+                    ("numberp", [term]) => {
+                        self.infer(term)?;
+                        Ok(Type::Bool)
+                    }
+                    // NOTE: Nothing is known of the other primitives but their arguments.
+                    _ => {
+                        for term in terms {
+                            self.infer(term)?;
+                        }
+                        Ok(self.fresh())
+                    }
+                }
+            }
+            /* Functions and Forms */
+            Term::Variable(name) => {
+                if let Some((_, ty)) = self.scope.iter().rev().find(|(other, _)| other == name) {
+                    return Ok(ty.clone());
+                }
+                match self.checker.globals.get(name) {
+                    Some(Scheme { ty, atomic }) => Ok(self.instantiate(ty, atomic)),
+                    None => Err(TypeErrorKind::UnboundVariable(name.clone())),
+                }
+            }
+            Term::Lambda(params, body) => {
+                let len = self.scope.len();
+                let mut types = vec![];
+                for param in params {
+                    let ty = self.fresh();
+                    types.push(ty.clone());
+                    self.scope.push((param.clone(), ty));
+                }
+                let ty = self.infer(body)?;
+                self.scope.truncate(len);
+                Ok(Type::Fn(types, Box::new(ty)))
+            }
+            Term::Apply(term, terms) => {
+                let ty = self.infer(term)?;
+                let mut types = vec![];
+                for term in terms {
+                    if let Term::UnquoteSplicing(_) = term.node() {
+                        return Err(TypeErrorKind::Unsupported("unquote-splicing"));
+                    }
+                    types.push(self.infer(term)?);
+                }
+                let result = self.fresh();
+                self.unify(&ty, &Type::Fn(types, Box::new(result.clone())))?;
+                Ok(result)
+            }
+            /* Expressions for Recursive Functions */
+            Term::Label(name, term) => {
+                let ty = self.fresh();
+                self.scope.push((name.clone(), ty.clone()));
+                self.check(term, &ty)?;
+                self.scope.pop();
+                Ok(ty)
+            }
+            Term::Labels(bindings, term) => {
+                let len = self.scope.len();
+                for (name, _) in bindings {
+                    let ty = self.fresh();
+                    self.scope.push((name.clone(), ty));
+                }
+                for (index, (_, term)) in bindings.iter().enumerate() {
+                    let ty = self.scope[len + index].1.clone();
+                    self.check(term, &ty)?;
+                }
+                let ty = self.infer(term)?;
+                self.scope.truncate(len);
+                Ok(ty)
+            }
+            /* M-expression */
+            Term::Quote(sexp) => Ok(self.type_of_sexpression(sexp)),
+            /* Debugging */
+            Term::Trace(_) | Term::Untrace(_) => Ok(Type::Atom),
+            /* Macros */
+            Term::Quasiquote(_) => Ok(Type::Code),
+            Term::Unquote(_) | Term::UnquoteSplicing(_) => {
+                Err(TypeErrorKind::Unsupported("unquote"))
+            }
+            Term::Defmacro(_, _, _) | Term::MacroCall(_, _) => {
+                Err(TypeErrorKind::Unsupported("a macro"))
+            }
+        }
+    }
+
+    fn type_of_sexpression(&mut self, sexp: &SExpression) -> Type {
+        match sexp {
+            SExpression::Symbol(name) if name == "T" || name == "F" => Type::Bool,
+            // NOTE: `NIL` is the empty list of anything, so that it can be returned in place of one.
+            SExpression::Symbol(name) if name == "NIL" => Type::List(Box::new(self.fresh())),
+            SExpression::Symbol(_) => Type::Atom,
+            SExpression::Integer(_) => Type::Integer,
+            SExpression::String(_) => Type::String,
+            SExpression::Pair(sexp1, sexp2) => Type::Pair(
+                Box::new(self.type_of_sexpression(sexp1)),
+                Box::new(self.type_of_sexpression(sexp2)),
+            ),
+        }
+    }
+
+    /// Infers the type of the component of `term` that an accessor with `path` takes.
+    fn infer_access(&mut self, path: &str, term: &Term) -> Result<Type, TypeErrorKind> {
+        let component = self.fresh();
+        let mut ty = component.clone();
+        for c in path.bytes() {
+            let other = Box::new(self.fresh());
+            ty = match c {
+                b'a' => Type::Pair(Box::new(ty), other),
+                _ => Type::Pair(other, Box::new(ty)),
+            };
+        }
+        self.check(term, &ty)?;
+        Ok(component)
+    }
+
+    /// Infers the type of `term` and matches it with `ty`.
+    fn check(&mut self, term: &Term, ty: &Type) -> Result<(), TypeErrorKind> {
+        let found = self.infer(term)?;
+        self.unify(ty, &found)
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Var(var) if *var < 26 => write!(f, "{}", (b'a' + *var as u8) as char),
+            Type::Var(var) => write!(f, "t{}", var),
+            Type::Atom => write!(f, "Atom"),
+            Type::Integer => write!(f, "Integer"),
+            Type::String => write!(f, "String"),
+            Type::Symbol => write!(f, "Symbol"),
+            Type::Bool => write!(f, "Bool"),
+            Type::List(ty) => write!(f, "List<{}>", ty),
+            Type::Pair(ty1, ty2) => write!(f, "Pair<{}, {}>", ty1, ty2),
+            Type::Fn(params, ty) => {
+                match params.as_slice() {
+                    [param @ Type::Fn(_, _)] => write!(f, "({})", param)?,
+                    [param] => write!(f, "{}", param)?,
+                    params => write!(
+                        f,
+                        "({})",
+                        params
+                            .iter()
+                            .map(|param| param.to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )?,
+                }
+                write!(f, " -> {}", ty)
+            }
+            Type::SExp => write!(f, "SExp"),
+            Type::Code => write!(f, "Code"),
+        }
+    }
+}
@@ -0,0 +1,136 @@
+mod sample_based_tests {
+    use pure_lisp::{
+        checker::{Checker, Lint},
+        environment::Environment,
+        lexer::{CaseMode, Lexer},
+        parser::Parser,
+        prelude,
+    };
+
+    /// The lints reported for `s`, with the lines and columns where they are found.
+    fn check(checker: &Checker, s: &str) -> Vec<(Lint, String)> {
+        let term = Parser::new(Lexer::new(s)).parse().unwrap();
+        checker
+            .check(&term)
+            .into_iter()
+            .map(|warning| (warning.lint, warning.span.unwrap().to_string()))
+            .collect()
+    }
+
+    fn checker() -> Checker {
+        Checker::new(&Environment::new())
+    }
+
+    #[test]
+    fn unbound_variable() {
+        assert_eq!(
+            check(&checker(), "(cons x (lambda (y) y))"),
+            vec![(Lint::UnboundVariable, "1:7".to_string())]
+        );
+        assert_eq!(
+            check(
+                &checker(),
+                "(labels ((f (lambda () (apply g ()))) (g (lambda () 'A))) f)"
+            ),
+            vec![]
+        );
+
+        let mut env = Environment::new();
+        prelude::load(&mut env);
+        assert_eq!(check(&Checker::new(&env), "(apply null (,'A))"), vec![]);
+        assert_eq!(
+            check(&Checker::new(&env), "(apply null (,'A ,'B))"),
+            vec![(Lint::ArityMismatch, "1:1".to_string())]
+        );
+    }
+
+    #[test]
+    fn arity_mismatch() {
+        assert_eq!(
+            check(&checker(), "(apply (lambda (x y) (cons x y)) (,'A))"),
+            vec![(Lint::ArityMismatch, "1:1".to_string())]
+        );
+        assert_eq!(
+            check(
+                &checker(),
+                "(apply (label f (lambda (x) (apply f (,x ,x)))) (,'A))"
+            ),
+            vec![
+                (Lint::UnprovenTermination, "1:8".to_string()),
+                (Lint::ArityMismatch, "1:29".to_string())
+            ]
+        );
+        // NOTE: The number of arguments of a parameter is unknown.
+        assert_eq!(check(&checker(), "(lambda (f) (apply f (,f ,f)))"), vec![]);
+    }
+
+    #[test]
+    fn unreachable_clause() {
+        assert_eq!(
+            check(&checker(), "(cond ('F 'A) ('T 'B) ('F 'C) ('T 'D))"),
+            vec![(Lint::UnreachableClause, "1:24".to_string())]
+        );
+        assert_eq!(check(&checker(), "(cond ('F 'A) ('T 'B))"), vec![]);
+    }
+
+    #[test]
+    fn eq_on_pair() {
+        assert_eq!(
+            check(&checker(), "(eq 'A '(A B))"),
+            vec![(Lint::EqOnPair, "1:1".to_string())]
+        );
+        assert_eq!(check(&checker(), "(eq 'A 'B)"), vec![]);
+
+        let mut checker = checker();
+        checker.case_mode = CaseMode::Downcase;
+        let term = Parser::new(Lexer::with_case_mode(
+            "(eq 'a '(b nil))",
+            CaseMode::Downcase,
+        ))
+        .parse()
+        .unwrap();
+        assert_eq!(
+            checker.check(&term)[0].message,
+            "eq of a quoted pair (b nil) is always an error"
+        );
+    }
+
+    #[test]
+    fn parameters() {
+        assert_eq!(
+            check(&checker(), "(lambda (x y) (lambda (x) (cons x y)))"),
+            vec![
+                (Lint::UnusedParameter, "1:1".to_string()),
+                (Lint::ShadowedParameter, "1:15".to_string())
+            ]
+        );
+        assert_eq!(
+            check(&checker(), "(lambda (x x) x)"),
+            vec![
+                (Lint::ShadowedParameter, "1:1".to_string()),
+                (Lint::UnusedParameter, "1:1".to_string())
+            ]
+        );
+        // NOTE: The body of a macro sees nothing but its parameters, and only its unquoted parts are evaluated.
+        assert_eq!(
+            check(
+                &checker(),
+                "(lambda (x) (defmacro m (a) `(lambda (y) (cons y ,a))))"
+            ),
+            vec![(Lint::UnusedParameter, "1:1".to_string())]
+        );
+    }
+
+    #[test]
+    fn allowed() {
+        let mut checker = checker();
+        checker.allowed.insert(Lint::UnusedParameter);
+        assert_eq!(
+            check(&checker, "(lambda (x) y)"),
+            vec![(Lint::UnboundVariable, "1:13".to_string())]
+        );
+
+        assert_eq!(Lint::from_code("L006"), Some(Lint::UnusedParameter));
+        assert_eq!(Lint::from_code("L999"), None);
+    }
+}
@@ -0,0 +1,181 @@
+mod sample_based_tests {
+    use pure_lisp::{
+        debugger::{Debugger, Stepper},
+        environment::Environment,
+        interpreter::Context,
+        lexer::{CaseMode, Lexer},
+        parser::Parser,
+        syntax::{Term, Value},
+    };
+    use std::{cell::RefCell, io, rc::Rc};
+
+    fn parse(s: &str) -> Term {
+        Parser::new(Lexer::new(s)).parse().unwrap()
+    }
+
+    fn last() -> &'static str {
+        "(apply (label last (lambda (xs) (cond ((atom (cdr xs)) (car xs)) ('T (apply last (,(cdr xs))))))) (,'(X (Y NIL))))"
+    }
+
+    #[derive(Clone, Default)]
+    struct Buffer(Rc<RefCell<Vec<u8>>>);
+
+    impl io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct Visits(Rc<RefCell<Vec<(usize, usize, String)>>>);
+
+    impl Debugger for Visits {
+        fn before_eval(&mut self, term: &Term, _: &Environment, ctx: &Context) {
+            self.0
+                .borrow_mut()
+                .push((ctx.depth(), ctx.frames().len(), term.to_string()));
+        }
+    }
+
+    fn debug(s: &str, commands: &[&str]) -> String {
+        let buffer = Buffer::default();
+        let mut ctx = Context::new();
+        ctx.debugger = Some(Box::new(Stepper::new(
+            io::Cursor::new(commands.join("\n")),
+            buffer.clone(),
+        )));
+        parse(s)
+            .eval_with(&mut Environment::new(), &mut ctx)
+            .unwrap();
+
+        let output = buffer.0.borrow().clone();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn hook() {
+        let visits = Visits::default();
+        let mut ctx = Context::new();
+        ctx.debugger = Some(Box::new(visits.clone()));
+        parse("(apply (lambda (x) (car x)) (,'(A B)))")
+            .eval_with(&mut Environment::new(), &mut ctx)
+            .unwrap();
+
+        assert_eq!(
+            *visits.0.borrow(),
+            vec![
+                (0, 0, "(apply (lambda (x) (car x)) (,'(A B)))".to_string()),
+                (1, 0, "(lambda (x) (car x))".to_string()),
+                (1, 0, "'(A B)".to_string()),
+                (1, 1, "(car x)".to_string()),
+                (2, 1, "x".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn step_next_finish() {
+        assert_eq!(
+            debug("(cons (car '(A B)) 'C)", &["s", "s", "", "s"]),
+            [
+                "at (cons (car '(A B)) 'C)",
+                "debug> at (car '(A B))",
+                "debug> at '(A B)",
+                "debug> at 'C",
+                "debug> "
+            ]
+            .join("\n")
+        );
+
+        assert_eq!(
+            debug("(cons (car '(A B)) 'C)", &["s", "n", "n"]),
+            [
+                "at (cons (car '(A B)) 'C)",
+                "debug> at (car '(A B))",
+                "debug> at 'C",
+                "debug> "
+            ]
+            .join("\n")
+        );
+
+        assert_eq!(
+            debug(
+                "(cons (apply (lambda (x) (car x)) (,'(A B))) 'C)",
+                &["s", "s", "s", "s", "f"]
+            ),
+            [
+                "at (cons (apply (lambda (x) (car x)) (,'(A B))) 'C)",
+                "debug> at (apply (lambda (x) (car x)) (,'(A B)))",
+                "debug> at (lambda (x) (car x))",
+                "debug> at '(A B)",
+                "debug> at (car x)",
+                "debug> at 'C",
+                "debug> "
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn case_mode() {
+        let buffer = Buffer::default();
+        let mut ctx = Context::new();
+        ctx.case_mode = CaseMode::Downcase;
+        ctx.debugger = Some(Box::new(Stepper::new(
+            io::Cursor::new(["e", "s", "s"].join("\n")),
+            buffer.clone(),
+        )));
+        let mut env = Environment::new();
+        env.extend("xs".to_string(), Value::Symbol("NIL".to_string()));
+        Parser::new(Lexer::with_case_mode("(cons 't '|t|)", CaseMode::Downcase))
+            .parse()
+            .unwrap()
+            .eval_with(&mut env, &mut ctx)
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(buffer.0.borrow().clone()).unwrap(),
+            [
+                "at (cons 't '|t|)",
+                "debug> xs = nil",
+                "debug> at 't",
+                "debug> at '|t|",
+                "debug> "
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn breakpoints() {
+        assert_eq!(
+            debug(last(), &["b last", "c", "bt", "e", "d last", "c"]),
+            [
+                &format!("at {}", last()),
+                "debug> debug> at (cond ((atom (cdr xs)) (car xs)) ('T (apply last (,(cdr xs)))))",
+                "debug> #0 (last (X (Y NIL))) at 1:1",
+                "debug> last = <closure>",
+                "xs = (X (Y NIL))",
+                "debug> debug> "
+            ]
+            .join("\n")
+        );
+
+        assert_eq!(
+            debug(last(), &["b last", "c", "c", "bt", "c"]),
+            [
+                &format!("at {}", last()),
+                "debug> debug> at (cond ((atom (cdr xs)) (car xs)) ('T (apply last (,(cdr xs)))))",
+                "debug> at (cond ((atom (cdr xs)) (car xs)) ('T (apply last (,(cdr xs)))))",
+                "debug> #0 (last (Y NIL)) at 1:70",
+                "#1 (last (X (Y NIL))) at 1:1",
+                "debug> "
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -0,0 +1,124 @@
+mod sample_based_tests {
+    use pure_lisp::{
+        environment::Environment,
+        error::{ErrorKind, MacroError, MacroErrorKind},
+        expander::Expander,
+        lexer::Lexer,
+        parser::Parser,
+        syntax::{Term, Value},
+    };
+
+    fn parse(s: &str) -> Term {
+        Parser::new(Lexer::new(s)).parse().unwrap()
+    }
+
+    fn expander(macros: &[&str]) -> Expander {
+        let mut expander = Expander::new();
+        for s in macros {
+            expander.expand(parse(s)).unwrap();
+        }
+        expander
+    }
+
+    fn unless() -> &'static str {
+        "(defmacro unless (p a b) `(cond (,p ,b) ('T ,a)))"
+    }
+
+    #[test]
+    fn defmacro() {
+        assert_eq!(
+            Expander::new().expand(parse(unless())),
+            Ok(parse("'unless"))
+        );
+    }
+
+    #[test]
+    fn expand() {
+        assert_eq!(
+            expander(&[unless()]).expand(parse("(unless (atom x) 'X 'Y)")),
+            Ok(parse("(cond ((atom x) 'Y) ('T 'X))"))
+        );
+
+        // NOTE: Macro calls in macro bodies, in arguments and in expansions are expanded as well.
+        assert_eq!(
+            expander(&[unless(), "(defmacro same (a) (unless 'F a 'NIL))"])
+                .expand(parse("(car (same (unless 'T 'X 'Y)))")),
+            Ok(parse("(car (cond ('T 'Y) ('T 'X)))"))
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let error = |kind| {
+            Err(MacroError {
+                name: "unless".to_string(),
+                kind,
+            })
+        };
+
+        assert_eq!(
+            expander(&[unless()]).expand(parse("(unless 'T 'X)")),
+            error(MacroErrorKind::ArityMismatch {
+                expected: 3,
+                found: 2
+            })
+        );
+
+        assert_eq!(
+            expander(&[]).expand(parse("(unless 'T 'X 'Y)")),
+            error(MacroErrorKind::UnknownMacro)
+        );
+
+        // NOTE: Errors in arguments and in the bodies of macros are reported as well.
+        assert_eq!(
+            expander(&[]).expand(parse("(car (unless 'T 'X 'Y))")),
+            error(MacroErrorKind::UnknownMacro)
+        );
+        assert_eq!(
+            expander(&["(defmacro unless (p a b) (car p))"]).expand(parse("(unless 'T 'X 'Y)")),
+            error(MacroErrorKind::Eval(Box::new(
+                ErrorKind::NotAPair("car", Value::Code(Box::new(parse("'T")))).into()
+            )))
+        );
+        assert_eq!(
+            expander(&["(defmacro unless (p a b) (lambda () p))"])
+                .expand(parse("(unless 'T 'X 'Y)"))
+                .map_err(|err| err.to_string()),
+            Err("macro unless expanded to <closure>, which is not a term".to_string())
+        );
+    }
+
+    #[test]
+    fn splicing() {
+        assert_eq!(
+            expander(&["(defmacro call (f a b) `(apply ,f (,@(cons a (cons b 'NIL)))))"])
+                .expand(parse("(call g x 'Y)")),
+            Ok(parse("(apply g (,x ,'Y))"))
+        );
+    }
+
+    #[test]
+    fn hygiene() {
+        let term = expander(&["(defmacro withx (e) `(apply (lambda (x) ,e) (,'INNER)))"])
+            .expand(parse("(apply (lambda (x) (withx x)) (,'OUTER))"))
+            .unwrap();
+
+        assert_eq!(
+            term.eval(&mut Environment::new()),
+            Some(Value::Symbol("OUTER".to_string()))
+        );
+    }
+
+    #[test]
+    fn quasiquote() {
+        let mut env = Environment::new();
+        env.extend("x".to_string(), Value::Symbol("X".to_string()));
+
+        assert_eq!(
+            parse("`(cons ,x y)").eval(&mut env),
+            Some(Value::Code(Box::new(parse("(cons 'X y)"))))
+        );
+
+        assert_eq!(parse(",x").eval(&mut env), None);
+    }
+}
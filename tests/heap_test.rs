@@ -0,0 +1,150 @@
+mod sample_based_tests {
+    use pure_lisp::{
+        environment::Environment,
+        heap::{Heap, Stats},
+        interpreter::{Context, Interpreter},
+        lexer::Lexer,
+        parser::Parser,
+        prelude,
+        syntax::{Term, Value},
+    };
+    use std::mem;
+
+    fn parse(s: &str) -> Term {
+        Parser::new(Lexer::new(s)).parse().unwrap()
+    }
+
+    fn ctx() -> Context {
+        let mut ctx = Context::new();
+        ctx.heap = Some(Heap::new());
+        ctx
+    }
+
+    fn eval(s: &str, env: &mut Environment, ctx: &mut Context) -> Value {
+        parse(s).eval_with(env, ctx).unwrap()
+    }
+
+    fn value(s: &str) -> Value {
+        parse(&format!("'{}", s))
+            .eval(&mut Environment::new())
+            .unwrap()
+    }
+
+    #[test]
+    fn cons() {
+        let mut ctx = ctx();
+        let mut env = Environment::new();
+
+        let list = eval("(cons 'A (cons 'B 'NIL))", &mut env, &mut ctx);
+        assert!(matches!(list, Value::Cell(_)));
+        assert_eq!(ctx.export(&list), value("(A (B NIL))"));
+        assert_eq!(ctx.heap.as_ref().unwrap().stats().live_cells, 2);
+
+        assert_eq!(
+            eval("(car (cdr (cons 'A (cons 'B 'NIL))))", &mut env, &mut ctx),
+            value("B")
+        );
+        assert_eq!(eval("(atom (cons 'A 'B))", &mut env, &mut ctx), value("F"));
+
+        prelude::load(&mut env);
+        let list = eval(
+            "(apply append (,(cons 'A 'NIL) ,'(B NIL)))",
+            &mut env,
+            &mut ctx,
+        );
+        assert_eq!(ctx.export(&list), value("(A (B NIL))"));
+    }
+
+    #[test]
+    fn collect() {
+        let mut ctx = ctx();
+        let mut env = Environment::new();
+
+        let list = eval("(cons 'A (cons 'B 'NIL))", &mut env, &mut ctx);
+        env.extend("list".to_string(), list);
+        // NOTE: Cells captured by a closure are reachable as well.
+        let closure = eval(
+            "(apply (lambda (x) (lambda () x)) (,(cons 'C 'NIL)))",
+            &mut env,
+            &mut ctx,
+        );
+        env.extend("closure".to_string(), closure);
+        eval("(cons 'D (cons 'E 'NIL))", &mut env, &mut ctx);
+
+        let heap = ctx.heap.as_mut().unwrap();
+        assert_eq!(heap.collect(&env), 2);
+        assert_eq!(
+            heap.stats(),
+            Stats {
+                live_cells: 3,
+                allocations: 5,
+                collections: 1,
+                cells_reclaimed: 2,
+                bytes_reclaimed: 2 * mem::size_of::<Option<(Value, Value)>>(),
+            }
+        );
+
+        let list = eval("list", &mut env, &mut ctx);
+        assert_eq!(ctx.export(&list), value("(A (B NIL))"));
+        let list = eval("(apply closure ())", &mut env, &mut ctx);
+        assert_eq!(ctx.export(&list), value("(C NIL)"));
+
+        // NOTE: Freed cells are reused.
+        for _ in 0..100 {
+            eval("(cons 'D (cons 'E 'NIL))", &mut env, &mut ctx);
+            ctx.heap.as_mut().unwrap().collect(&env);
+        }
+        assert_eq!(ctx.heap.as_ref().unwrap().stats().live_cells, 3);
+        assert!(matches!(
+            eval("(cons 'F 'NIL)", &mut env, &mut ctx),
+            Value::Cell(index) if index < 5
+        ));
+    }
+
+    #[test]
+    fn collect_during_evaluation() {
+        let mut ctx = ctx();
+        let mut env = Environment::new();
+
+        // NOTE: Each step allocates a tree that is garbage right away, while the pair made before
+        // it and the accumulated list are still in use by the evaluation.
+        let list = eval(
+            "(labels ((tree (lambda (n)
+                        (if (= n 0)
+                            'NIL
+                            (cons (apply tree (,(- n 1))) (apply tree (,(- n 1)))))))
+                      (loop (lambda (n acc)
+                        (if (= n 0)
+                            acc
+                            (apply loop (,(- n 1)
+                                         ,(cons (car (cons (cons n 'NIL) (apply tree (,9))))
+                                                acc)))))))
+               (apply loop (,8 ,'NIL)))",
+            &mut env,
+            &mut ctx,
+        );
+
+        let stats = ctx.heap.as_ref().unwrap().stats();
+        assert!(stats.collections > 0);
+        assert!(stats.cells_reclaimed > 0);
+        let expected = (1..=8)
+            .rev()
+            .fold("NIL".to_string(), |acc, n| format!("(({} NIL) {})", n, acc));
+        assert_eq!(ctx.export(&list), value(&expected));
+    }
+
+    #[test]
+    fn collect_between_terms() {
+        let mut interpreter = Interpreter::new();
+        interpreter.ctx.heap = Some(Heap::new());
+
+        // NOTE: No term applies a function, so the heap can only be collected between them.
+        let source = "(cons 'A (cons 'B 'NIL))\n".repeat(1000);
+        let list = interpreter.eval_str(&source).unwrap();
+
+        assert_eq!(list, value("(A (B NIL))"));
+        let stats = interpreter.ctx.heap.as_ref().unwrap().stats();
+        assert!(stats.collections > 0);
+        assert!(stats.live_cells < 2000);
+    }
+}
@@ -0,0 +1,75 @@
+use pure_lisp::integer::Integer;
+
+#[cfg(test)]
+#[macro_use]
+extern crate quickcheck;
+
+/*********************************************
+Testing
+**********************************************/
+#[cfg(test)]
+mod property_based_tests {
+    use crate::Integer;
+
+    // NOTE: The products of `i32`s fit in an `i64`, which the results are compared with.
+    quickcheck! {
+        fn arithmetic(n1: i32, n2: i32) -> bool {
+            let (n1, n2) = (i64::from(n1), i64::from(n2));
+            let (m1, m2) = (Integer::from(n1), Integer::from(n2));
+            &m1 + &m2 == Integer::from(n1 + n2)
+                && &m1 - &m2 == Integer::from(n1 - n2)
+                && &m1 * &m2 == Integer::from(n1 * n2)
+                && m1.cmp(&m2) == n1.cmp(&n2)
+        }
+
+        fn division(n1: i64, n2: i32) -> bool {
+            let n2 = i64::from(n2);
+            match Integer::from(n1).div_rem(&Integer::from(n2)) {
+                Some((quotient, remainder)) => {
+                    quotient == Integer::from(n1 / n2) && remainder == Integer::from(n1 % n2)
+                }
+                None => n2 == 0,
+            }
+        }
+
+        fn printing(n: i64) -> bool {
+            Integer::from(n).to_string() == n.to_string()
+                && n.to_string().parse() == Ok(Integer::from(n))
+        }
+    }
+}
+
+mod sample_based_tests {
+    use pure_lisp::integer::{Integer, ParseIntegerError};
+
+    fn integer(s: &str) -> Integer {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(integer("+42"), Integer::from(42));
+        assert_eq!(integer("-0"), Integer::from(0));
+        assert_eq!(integer("000123"), Integer::from(123));
+        assert_eq!("12a".parse::<Integer>(), Err(ParseIntegerError));
+        assert_eq!("-".parse::<Integer>(), Err(ParseIntegerError));
+    }
+
+    #[test]
+    fn big() {
+        let n1 = integer("123456789012345678901234567890");
+        let n2 = integer("-987654321987654321");
+        assert_eq!(
+            (&n1 * &n2).to_string(),
+            "-121932631246761163237311385323609205901126352690"
+        );
+        assert_eq!((&n1 + &n2).to_string(), "123456789011358024579246913569");
+        assert_eq!((&n2 - &n1).to_string(), "-123456789013333333223222222211");
+
+        let (quotient, remainder) = n1.div_rem(&n2).unwrap();
+        assert_eq!(quotient.to_string(), "-124999998748");
+        assert_eq!(remainder.to_string(), "432099904777777782");
+        assert_eq!(&(&quotient * &n2) + &remainder, n1);
+        assert_eq!(n1.div_rem(&Integer::from(0)), None);
+    }
+}
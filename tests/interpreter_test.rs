@@ -10,7 +10,7 @@ struct SExpressionWrapper(SExpression);
 
 impl Arbitrary for SExpressionWrapper {
     fn arbitrary(g: &mut Gen) -> Self {
-        if g.choose(&[true, false]).unwrap().clone() {
+        if *g.choose(&[true, false]).unwrap() {
             SExpressionWrapper(SExpression::Symbol(String::arbitrary(g)))
         } else {
             SExpressionWrapper(SExpression::Pair(
@@ -27,6 +27,11 @@ Testing
 #[cfg(test)]
 mod property_based_tests {
     use crate::SExpressionWrapper;
+    use pure_lisp::{
+        lexer::{CaseMode, Lexer},
+        parser::Parser,
+        syntax::{Printed, SExpression, Term},
+    };
 
     quickcheck! {
         fn halting(exp: SExpressionWrapper) -> bool {
@@ -37,14 +42,38 @@ mod property_based_tests {
         fn uniqueness(exp: SExpressionWrapper) -> bool {
             exp.clone().0.eval() == exp.0.eval()
         }
+
+        // NOTE: Any symbol is printed so that it is read back, between bars if need be.
+        fn reads_back(name: String) -> bool {
+            let sexp = SExpression::Symbol(name);
+            let source = format!("'{}", sexp.clone().eval());
+            let term = Parser::new(Lexer::new(&source)).parse();
+            term == Some(Term::Quote(sexp))
+        }
+
+        fn reads_back_in_case_modes(name: String) -> bool {
+            let sexp = SExpression::Symbol(name);
+            [CaseMode::Preserve, CaseMode::Upcase, CaseMode::Downcase]
+                .into_iter()
+                .all(|case_mode| {
+                    let source = format!("'{}", Printed(case_mode, &sexp));
+                    let term = Parser::new(Lexer::with_case_mode(&source, case_mode)).parse();
+                    term == Some(Term::Quote(sexp.clone()))
+                })
+        }
     }
 }
 
 mod sample_based_tests {
     use pure_lisp::{
         environment::Environment,
-        syntax::{SExpression, Term, Value},
+        error::{ErrorKind, EvalError, InterpreterError},
+        interpreter::{BooleanMode, Context, Frame, Interpreter},
+        lexer::CaseMode,
+        primitive,
+        syntax::{Captured, Closure, Printed, SExpression, Term, Value},
     };
+    use std::rc::Rc;
 
     fn env() -> Environment {
         Environment::new()
@@ -57,16 +86,19 @@ mod sample_based_tests {
         Box::new(Term::Quote(SExpression::Symbol("F".to_string())))
     }
     fn dummy_atom(id: &str) -> Box<Term> {
-        Box::new(Term::Quote(SExpression::Symbol(
-            format!("{}", id).to_string(),
-        )))
+        Box::new(Term::Quote(SExpression::Symbol(id.to_string())))
     }
     fn dummy_pair(id1: &str, id2: &str) -> Box<Term> {
         Box::new(Term::Quote(SExpression::Pair(
-            Box::new(SExpression::Symbol(format!("{}", id1))),
-            Box::new(SExpression::Symbol(format!("{}", id2))),
+            Box::new(SExpression::Symbol(id1.to_string())),
+            Box::new(SExpression::Symbol(id2.to_string())),
         )))
     }
+    // NOTE: The arguments are boxed as in `Term::Primitive`.
+    #[allow(clippy::vec_box)]
+    fn primitive(name: &str, terms: Vec<Box<Term>>) -> Term {
+        Term::Primitive(primitive::lookup(name).unwrap(), terms)
+    }
     fn target() -> Box<Term> {
         Box::new(Term::Quote(SExpression::Symbol("TARGET".to_string())))
     }
@@ -106,7 +138,7 @@ mod sample_based_tests {
 
         assert_eq!(
             Term::Cond(vec![
-                (f(), Box::new(Term::Car(dummy_atom("")))),
+                (f(), Box::new(primitive("car", vec![dummy_atom("")]))),
                 (t(), target())
             ])
             .eval(&mut env()),
@@ -116,7 +148,7 @@ mod sample_based_tests {
         assert_eq!(
             Term::Cond(vec![
                 (f(), dummy_atom("1")),
-                (t(), Box::new(Term::Car(dummy_atom("2"))))
+                (t(), Box::new(primitive("car", vec![dummy_atom("2")])))
             ])
             .eval(&mut env()),
             None
@@ -128,12 +160,111 @@ mod sample_based_tests {
         );
     }
 
+    #[test]
+    fn boolean_modes() {
+        let mut strict = Context::new();
+        strict.boolean_mode = BooleanMode::Strict;
+        let mut lisp15 = Context::new();
+        lisp15.boolean_mode = BooleanMode::Lisp15;
+        let nil = || dummy_atom("NIL");
+
+        assert_eq!(
+            Term::Cond(vec![(f(), dummy_atom("")), (t(), target())])
+                .eval_with(&mut env(), &mut strict),
+            Ok(target_value())
+        );
+        assert_eq!(
+            Term::Cond(vec![(f(), dummy_atom("")), (nil(), target())])
+                .eval_with(&mut env(), &mut strict),
+            Err(ErrorKind::NonBooleanPredicate {
+                form: "cond",
+                index: 1,
+                value: Value::Symbol("NIL".to_string())
+            }
+            .into())
+        );
+        assert_eq!(
+            Term::And(vec![t(), dummy_pair("1", "2")]).eval_with(&mut env(), &mut strict),
+            Err(ErrorKind::NonBooleanPredicate {
+                form: "and",
+                index: 1,
+                value: dummy_pair("1", "2").eval(&mut env()).unwrap()
+            }
+            .into())
+        );
+
+        assert_eq!(
+            Term::Cond(vec![(nil(), dummy_atom("")), (f(), target())])
+                .eval_with(&mut env(), &mut lisp15),
+            Ok(target_value())
+        );
+        assert_eq!(
+            primitive("atom", vec![dummy_pair("1", "2")]).eval_with(&mut env(), &mut lisp15),
+            nil().eval_with(&mut env(), &mut lisp15)
+        );
+        assert_eq!(
+            Term::Not(nil()).eval_with(&mut env(), &mut lisp15),
+            Ok(t_value())
+        );
+    }
+
+    #[test]
+    fn and_or() {
+        assert_eq!(Term::And(vec![t(), t()]).eval(&mut env()), Some(t_value()));
+        assert_eq!(Term::And(vec![t(), f()]).eval(&mut env()), Some(f_value()));
+        assert_eq!(Term::And(vec![]).eval(&mut env()), Some(t_value()));
+        assert_eq!(Term::Or(vec![f(), t()]).eval(&mut env()), Some(t_value()));
+        assert_eq!(Term::Or(vec![f(), f()]).eval(&mut env()), Some(f_value()));
+        assert_eq!(Term::Or(vec![]).eval(&mut env()), Some(f_value()));
+
+        // NOTE: Evaluation stops at the first operand that decides the result.
+        assert_eq!(
+            Term::And(vec![f(), Box::new(primitive("car", vec![dummy_atom("")]))]).eval(&mut env()),
+            Some(f_value())
+        );
+        assert_eq!(
+            Term::Or(vec![t(), Box::new(primitive("car", vec![dummy_atom("")]))]).eval(&mut env()),
+            Some(t_value())
+        );
+        assert_eq!(
+            Term::And(vec![t(), Box::new(primitive("car", vec![dummy_atom("")]))]).eval(&mut env()),
+            None
+        );
+    }
+
+    #[test]
+    fn not() {
+        assert_eq!(Term::Not(t()).eval(&mut env()), Some(f_value()));
+        assert_eq!(Term::Not(f()).eval(&mut env()), Some(t_value()));
+        assert_eq!(Term::Not(dummy_atom("")).eval(&mut env()), Some(t_value()));
+    }
+
+    #[test]
+    fn if_() {
+        assert_eq!(
+            Term::If(t(), target(), Some(dummy_atom(""))).eval(&mut env()),
+            Some(target_value())
+        );
+        assert_eq!(
+            Term::If(f(), dummy_atom(""), Some(target())).eval(&mut env()),
+            Some(target_value())
+        );
+        assert_eq!(
+            Term::If(t(), target(), None).eval(&mut env()),
+            Some(target_value())
+        );
+        assert_eq!(Term::If(f(), target(), None).eval(&mut env()), None);
+    }
+
     #[test]
     fn atom() {
-        assert_eq!(Term::Atom(dummy_atom("")).eval(&mut env()), Some(t_value()));
+        assert_eq!(
+            primitive("atom", vec![dummy_atom("")]).eval(&mut env()),
+            Some(t_value())
+        );
 
         assert_eq!(
-            Term::Atom(dummy_pair("1", "2")).eval(&mut env()),
+            primitive("atom", vec![dummy_pair("1", "2")]).eval(&mut env()),
             Some(f_value())
         );
     }
@@ -141,22 +272,22 @@ mod sample_based_tests {
     #[test]
     fn eq() {
         assert_eq!(
-            Term::Eq(dummy_atom(""), dummy_atom("")).eval(&mut env()),
+            primitive("eq", vec![dummy_atom(""), dummy_atom("")]).eval(&mut env()),
             Some(t_value())
         );
 
         assert_eq!(
-            Term::Eq(dummy_atom("1"), dummy_atom("2")).eval(&mut env()),
+            primitive("eq", vec![dummy_atom("1"), dummy_atom("2")]).eval(&mut env()),
             Some(f_value())
         );
 
         assert_eq!(
-            Term::Eq(dummy_atom(""), dummy_pair("1", "2")).eval(&mut env()),
+            primitive("eq", vec![dummy_atom(""), dummy_pair("1", "2")]).eval(&mut env()),
             None
         );
 
         assert_eq!(
-            Term::Eq(dummy_pair("1", "2"), dummy_pair("3", "4")).eval(&mut env()),
+            primitive("eq", vec![dummy_pair("1", "2"), dummy_pair("3", "4")]).eval(&mut env()),
             None
         );
     }
@@ -164,27 +295,33 @@ mod sample_based_tests {
     #[test]
     fn car() {
         assert_eq!(
-            Term::Car(dummy_pair("1", "2")).eval(&mut env()),
+            primitive("car", vec![dummy_pair("1", "2")]).eval(&mut env()),
             Some(Value::Symbol("1".to_string()))
         );
 
-        assert_eq!(Term::Car(dummy_atom("")).eval(&mut env()), None);
+        assert_eq!(
+            primitive("car", vec![dummy_atom("")]).eval(&mut env()),
+            None
+        );
     }
 
     #[test]
     fn cdr() {
         assert_eq!(
-            Term::Cdr(dummy_pair("1", "2")).eval(&mut env()),
+            primitive("cdr", vec![dummy_pair("1", "2")]).eval(&mut env()),
             Some(Value::Symbol("2".to_string()))
         );
 
-        assert_eq!(Term::Car(dummy_atom("")).eval(&mut env()), None);
+        assert_eq!(
+            primitive("car", vec![dummy_atom("")]).eval(&mut env()),
+            None
+        );
     }
 
     #[test]
     fn cons() {
         assert_eq!(
-            Term::Cons(dummy_atom("1"), dummy_atom("2")).eval(&mut env()),
+            primitive("cons", vec![dummy_atom("1"), dummy_atom("2")]).eval(&mut env()),
             Some(Value::Pair(
                 Box::new(Value::Symbol("1".to_string())),
                 Box::new(Value::Symbol("2".to_string()))
@@ -192,7 +329,7 @@ mod sample_based_tests {
         );
 
         assert_eq!(
-            Term::Cons(dummy_atom("1"), dummy_pair("2", "3")).eval(&mut env()),
+            primitive("cons", vec![dummy_atom("1"), dummy_pair("2", "3")]).eval(&mut env()),
             Some(Value::Pair(
                 Box::new(Value::Symbol("1".to_string())),
                 Box::new(Value::Pair(
@@ -203,6 +340,216 @@ mod sample_based_tests {
         );
     }
 
+    #[test]
+    fn integers() {
+        let mut interpreter = Interpreter::new();
+        let mut eval = |s: &str| {
+            interpreter
+                .eval_str(s)
+                .map(|value| value.to_string())
+                .map_err(|err| err.to_string())
+        };
+
+        assert_eq!(eval("42"), Ok("42".to_string()));
+        assert_eq!(eval("'(1 (-2 NIL))"), Ok("(1 (-2 NIL))".to_string()));
+        assert_eq!(eval("(- (+ 1 2) (* 3 4))"), Ok("-9".to_string()));
+        assert_eq!(
+            eval("(apply (label fact (lambda (n) (if (= n 0) 1 (* n (apply fact (,(- n 1))))))) (,25))"),
+            Ok("15511210043330985984000000".to_string())
+        );
+        assert_eq!(eval("(quotient 7 -2)"), Ok("-3".to_string()));
+        assert_eq!(eval("(remainder 7 -2)"), Ok("1".to_string()));
+        assert_eq!(eval("(< 1 2)"), Ok("T".to_string()));
+        assert_eq!(eval("(> 1 2)"), Ok("F".to_string()));
+        assert_eq!(eval("(apply + (,1 ,2))"), Ok("3".to_string()));
+        // NOTE: Integers are atoms, and `eq` compares them by value.
+        assert_eq!(eval("(atom 1)"), Ok("T".to_string()));
+        assert_eq!(eval("(numberp 1)"), Ok("T".to_string()));
+        assert_eq!(eval("(numberp 'A)"), Ok("F".to_string()));
+        assert_eq!(eval("(eq 10 '10)"), Ok("T".to_string()));
+        assert_eq!(eval("(eq 'A 1)"), Ok("F".to_string()));
+
+        assert_eq!(eval("(+ 1 'A)"), Err("+ of a non-integer A".to_string()));
+        assert_eq!(
+            eval("(remainder 1 0)"),
+            Err("remainder by zero".to_string())
+        );
+    }
+
+    #[test]
+    fn strings() {
+        let mut interpreter = Interpreter::new();
+        let mut eval = |s: &str| {
+            interpreter
+                .eval_str(s)
+                .map(|value| value.to_string())
+                .map_err(|err| err.to_string())
+        };
+
+        assert_eq!(eval(r#""a\tb""#), Ok(r#""a\tb""#.to_string()));
+        assert_eq!(
+            eval(r#"(string-append "ab" "cd")"#),
+            Ok(r#""abcd""#.to_string())
+        );
+        assert_eq!(eval("(explode 'abc)"), Ok("(a (b (c NIL)))".to_string()));
+        assert_eq!(eval("(implode '(a (b (c NIL))))"), Ok("abc".to_string()));
+        assert_eq!(
+            eval("(implode (cons 'X (explode 'YZ)))"),
+            Ok("XYZ".to_string())
+        );
+        assert_eq!(eval("(implode 'NIL)"), Ok("||".to_string()));
+        assert_eq!(eval("(explode '||)"), Ok("NIL".to_string()));
+        assert_eq!(eval("(symbol->string 'FOO)"), Ok(r#""FOO""#.to_string()));
+        assert_eq!(
+            eval(r#"(string->symbol (string-append "A" "B"))"#),
+            Ok("AB".to_string())
+        );
+        // NOTE: Strings are atoms, and `eq` compares them by value.
+        assert_eq!(eval(r#"(atom "x")"#), Ok("T".to_string()));
+        assert_eq!(eval(r#"(eq "x" "x")"#), Ok("T".to_string()));
+        assert_eq!(eval(r#"(eq "x" 'x)"#), Ok("F".to_string()));
+
+        assert_eq!(
+            eval(r#"(explode "abc")"#),
+            Err(r#"explode of a non-symbol "abc""#.to_string())
+        );
+        assert_eq!(
+            eval("(implode '(AB NIL))"),
+            Err("implode of a list with a non-character AB".to_string())
+        );
+        assert_eq!(
+            eval("(string-append 'A 'B)"),
+            Err("string-append of a non-string A".to_string())
+        );
+    }
+
+    #[test]
+    fn symbols() {
+        let mut interpreter = Interpreter::new();
+        let mut eval = |s: &str| {
+            interpreter
+                .eval_str(s)
+                .map(|value| value.to_string())
+                .map_err(|err| err.to_string())
+        };
+
+        assert_eq!(
+            eval("(apply (lambda (null? *x* 1+) (cons null? (cons *x* 1+))) (,'a ,'b ,'c))"),
+            Ok("(a (b c))".to_string())
+        );
+        // NOTE: A symbol that would not be read back is printed between bars.
+        assert_eq!(eval("'|hello world|"), Ok("|hello world|".to_string()));
+        assert_eq!(eval(r#"(string->symbol "12")"#), Ok("|12|".to_string()));
+        assert_eq!(eval(r#"(string->symbol "")"#), Ok("||".to_string()));
+        assert_eq!(eval(r"'|a\|b|"), Ok(r"|a\|b|".to_string()));
+        assert_eq!(eval("'(lambda λ)"), Ok("(lambda λ)".to_string()));
+    }
+
+    #[test]
+    fn case_modes() {
+        let mut upcase = Interpreter::with_case_mode(CaseMode::Upcase);
+        let mut eval = |s: &str| {
+            upcase
+                .eval_str(s)
+                .map(|value| upcase.print(&value))
+                .map_err(|err| err.to_string())
+        };
+
+        assert_eq!(eval("(eq 'x 'X)"), Ok("T".to_string()));
+        assert_eq!(eval("(cadr '(a (b NIL)))"), Ok("B".to_string()));
+        assert_eq!(
+            eval("(apply append (,'(a nil) ,'(b nil)))"),
+            Ok("(A (B NIL))".to_string())
+        );
+        assert_eq!(
+            eval("(apply (LAMBDA (x) (Cons X '|x|)) (,'y))"),
+            Ok("(Y |x|)".to_string())
+        );
+        upcase.define("answer", Value::Symbol("YES".to_string()));
+        assert_eq!(upcase.get("ANSWER"), Some(Value::Symbol("YES".to_string())));
+        assert_eq!(upcase.eval_str("answer").unwrap().to_string(), "YES");
+
+        let mut downcase = Interpreter::with_case_mode(CaseMode::Downcase);
+        let mut eval = |s: &str| {
+            downcase
+                .eval_str(s)
+                .map(|value| downcase.print(&value))
+                .map_err(|err| err.to_string())
+        };
+
+        // NOTE: The atoms of the interpreter are read and printed in lowercase as well.
+        assert_eq!(eval("(eq 'A 'a)"), Ok("t".to_string()));
+        assert_eq!(eval("(atom '(a nil))"), Ok("f".to_string()));
+        assert_eq!(eval("'Nil"), Ok("nil".to_string()));
+        assert_eq!(
+            eval("(apply Append (,'(A nil) ,'(B nil)))"),
+            Ok("(a (b nil))".to_string())
+        );
+        assert_eq!(eval("(apply null (,'nil))"), Ok("t".to_string()));
+        assert_eq!(
+            eval("(apply equal (,'(a (b nil)) ,'(A (B NIL))))"),
+            Ok("t".to_string())
+        );
+        assert_eq!(
+            eval("(apply among (,'c ,'(a (b nil))))"),
+            Ok("f".to_string())
+        );
+        assert_eq!(
+            eval("(apply subst (,'x ,'a ,'(a (b nil))))"),
+            Ok("(x (b nil))".to_string())
+        );
+        assert_eq!(eval("(cond ((eq 'x 'y) 'a) ('t 'b))"), Ok("b".to_string()));
+        // NOTE: A symbol spelled like them between bars is another symbol.
+        assert_eq!(eval("'|nil|"), Ok("|nil|".to_string()));
+        assert_eq!(eval("(eq '|nil| 'nil)"), Ok("f".to_string()));
+
+        // NOTE: The values in an error are written in the case mode, but only where it is given.
+        let err = match downcase.eval_str("(car 'nil)") {
+            Err(InterpreterError::Eval(err)) => err,
+            result => panic!("unexpected {:?}", result),
+        };
+        assert_eq!(
+            Printed(CaseMode::Downcase, &err).to_string(),
+            "car of an atom nil"
+        );
+        assert_eq!(err.to_string(), "car of an atom NIL");
+
+        let mut preserve = Interpreter::new();
+        assert_eq!(preserve.eval_str("(eq 'x 'X)").unwrap().to_string(), "F");
+    }
+
+    #[test]
+    fn accessors() {
+        let mut interpreter = Interpreter::new();
+        let mut eval = |s: &str| {
+            interpreter
+                .eval_str(s)
+                .map(|value| value.to_string())
+                .map_err(|err| err.to_string())
+        };
+
+        assert_eq!(eval("(cadr '(A (B NIL)))"), Ok("B".to_string()));
+        assert_eq!(eval("(caddr '(A (B (C NIL))))"), Ok("C".to_string()));
+        assert_eq!(eval("(cdddr '(A (B (C NIL))))"), Ok("NIL".to_string()));
+        assert_eq!(eval("(caadr '(A ((B C) NIL)))"), Ok("B".to_string()));
+        // NOTE: The error tells which step of the composition hit an atom.
+        assert_eq!(
+            eval("(caddr '(A (B NIL)))"),
+            Err("car of an atom NIL at step 3 of caddr".to_string())
+        );
+        assert_eq!(
+            eval("(cadddr '(A (B NIL)))"),
+            Err("cdr of an atom NIL at step 3 of cadddr".to_string())
+        );
+        // NOTE: Only up to four steps are recognised.
+        assert_eq!(eval("(caddddr '(A (B NIL)))").ok(), None);
+        // NOTE: The accessors are bound to functions like the other primitives.
+        assert_eq!(
+            eval("(apply (lambda (f x) (apply f (,x))) (,cadr ,'(A (B NIL))))"),
+            Ok("B".to_string())
+        );
+    }
+
     #[test]
     fn variable() {
         assert_eq!(
@@ -276,9 +623,12 @@ mod sample_based_tests {
                         vec!["x".to_string()],
                         Box::new(Term::Lambda(
                             vec!["y".to_string()],
-                            Box::new(Term::Cons(
-                                Box::new(Term::Variable("x".to_string())),
-                                Box::new(Term::Variable("y".to_string()))
+                            Box::new(primitive(
+                                "cons",
+                                vec![
+                                    Box::new(Term::Variable("x".to_string())),
+                                    Box::new(Term::Variable("y".to_string()))
+                                ]
                             ))
                         ))
                     )),
@@ -300,16 +650,20 @@ mod sample_based_tests {
                         vec!["x".to_string()],
                         Box::new(Term::Cond(vec![
                             (
-                                Box::new(Term::Atom(Box::new(Term::Variable("x".to_string())))),
+                                Box::new(primitive(
+                                    "atom",
+                                    vec![Box::new(Term::Variable("x".to_string()))]
+                                )),
                                 Box::new(Term::Variable("x".to_string()))
                             ),
                             (
                                 t(),
                                 Box::new(Term::Apply(
                                     Box::new(Term::Variable("last".to_string())),
-                                    vec![Box::new(Term::Cdr(Box::new(Term::Variable(
-                                        "x".to_string()
-                                    ))))]
+                                    vec![Box::new(primitive(
+                                        "cdr",
+                                        vec![Box::new(Term::Variable("x".to_string()))]
+                                    ))]
                                 ))
                             )
                         ]))
@@ -327,4 +681,382 @@ mod sample_based_tests {
             Some(Value::Symbol("3".to_string()))
         );
     }
+
+    #[test]
+    fn labels() {
+        // NOTE: `evenp` and `oddp` call each other on the tail of the list.
+        let parity = |name: &str, base: Box<Term>, other: &str| {
+            (
+                name.to_string(),
+                Box::new(Term::Lambda(
+                    vec!["x".to_string()],
+                    Box::new(Term::Cond(vec![
+                        (
+                            Box::new(primitive(
+                                "atom",
+                                vec![Box::new(Term::Variable("x".to_string()))],
+                            )),
+                            base,
+                        ),
+                        (
+                            t(),
+                            Box::new(Term::Apply(
+                                Box::new(Term::Variable(other.to_string())),
+                                vec![Box::new(primitive(
+                                    "cdr",
+                                    vec![Box::new(Term::Variable("x".to_string()))],
+                                ))],
+                            )),
+                        ),
+                    ])),
+                )),
+            )
+        };
+        let evenp = |list: Box<Term>| {
+            Term::Labels(
+                vec![parity("evenp", t(), "oddp"), parity("oddp", f(), "evenp")],
+                Box::new(Term::Apply(
+                    Box::new(Term::Variable("evenp".to_string())),
+                    vec![list],
+                )),
+            )
+        };
+
+        assert_eq!(
+            evenp(Box::new(Term::Quote(SExpression::Pair(
+                Box::new(SExpression::Symbol("1".to_string())),
+                Box::new(SExpression::Pair(
+                    Box::new(SExpression::Symbol("2".to_string())),
+                    Box::new(SExpression::Symbol("NIL".to_string()))
+                ))
+            ))))
+            .eval(&mut env()),
+            Some(t_value())
+        );
+
+        assert_eq!(
+            evenp(dummy_pair("1", "NIL")).eval(&mut env()),
+            Some(f_value())
+        );
+
+        assert_eq!(
+            Term::Labels(
+                vec![("x".to_string(), target())],
+                Box::new(Term::Variable("x".to_string()))
+            )
+            .eval(&mut env()),
+            None
+        );
+    }
+
+    #[test]
+    fn backtrace() {
+        // NOTE: `(car x)` is wrong when `x` is an atom, which is found in the innermost application.
+        let last = Term::Label(
+            "last".to_string(),
+            Box::new(Term::Lambda(
+                vec!["x".to_string()],
+                Box::new(Term::Cond(vec![
+                    (
+                        Box::new(primitive(
+                            "atom",
+                            vec![Box::new(Term::Variable("x".to_string()))],
+                        )),
+                        Box::new(primitive(
+                            "car",
+                            vec![Box::new(Term::Variable("x".to_string()))],
+                        )),
+                    ),
+                    (
+                        t(),
+                        Box::new(Term::Apply(
+                            Box::new(Term::Variable("last".to_string())),
+                            vec![Box::new(primitive(
+                                "cdr",
+                                vec![Box::new(Term::Variable("x".to_string()))],
+                            ))],
+                        )),
+                    ),
+                ])),
+            )),
+        );
+        let frame = |name: &str, value: Value| Frame {
+            name: Some(name.to_string()),
+            args: vec![value],
+            span: None,
+        };
+
+        assert_eq!(
+            Term::Apply(Box::new(last), vec![dummy_pair("1", "NIL")])
+                .eval_with(&mut env(), &mut Context::new()),
+            Err(EvalError {
+                kind: ErrorKind::NotAPair("car", Value::Symbol("NIL".to_string())),
+                backtrace: vec![
+                    frame("last", dummy_pair("1", "NIL").eval(&mut env()).unwrap()),
+                    frame("last", Value::Symbol("NIL".to_string()))
+                ]
+            })
+        );
+
+        assert_eq!(
+            primitive("car", vec![dummy_atom("A")]).eval_with(&mut env(), &mut Context::new()),
+            Err(ErrorKind::NotAPair("car", Value::Symbol("A".to_string())).into())
+        );
+    }
+
+    #[test]
+    fn label_debug_eq() {
+        let last = || {
+            Term::Label(
+                "last".to_string(),
+                Box::new(Term::Lambda(
+                    vec!["x".to_string()],
+                    Box::new(Term::Apply(
+                        Box::new(Term::Variable("last".to_string())),
+                        vec![Box::new(Term::Variable("x".to_string()))],
+                    )),
+                )),
+            )
+            .eval(&mut env())
+            .unwrap()
+        };
+
+        let value = last();
+        assert!(format!("{:?}", value).starts_with("Closure(Closure {"));
+        assert_eq!(value, value.clone());
+        // NOTE: Closures are told apart by their environments.
+        assert_ne!(value, last());
+
+        let mut env = Environment::new();
+        env.extend("last".to_string(), value);
+        assert!(format!("{:?}", env).contains("Closure(Closure {"));
+        assert_eq!(env, env.clone());
+    }
+
+    #[test]
+    fn label_memory() {
+        let var = |name: &str| Box::new(Term::Variable(name.to_string()));
+        // NOTE: `(label f (lambda (x) (lambda () (apply f (,x)))))`
+        let label = || {
+            Term::Label(
+                "f".to_string(),
+                Box::new(Term::Lambda(
+                    vec!["x".to_string()],
+                    Box::new(Term::Lambda(
+                        vec![],
+                        Box::new(Term::Apply(var("f"), vec![var("x")])),
+                    )),
+                )),
+            )
+        };
+        let environment = |value: &Value| match value {
+            Value::Closure(Closure {
+                env: Captured::Strong(env),
+                ..
+            }) => Rc::downgrade(env),
+            _ => panic!("A closure should hold its environment strongly"),
+        };
+
+        let mut envs = vec![];
+        for _ in 0..1000 {
+            let value = label().eval(&mut env()).unwrap();
+            envs.push(environment(&value));
+        }
+        assert!(envs.iter().all(|env| env.upgrade().is_none()));
+
+        // NOTE: A closure returned from its own body keeps its environment alive.
+        let value = Term::Apply(
+            Box::new(Term::Apply(Box::new(label()), vec![target()])),
+            vec![],
+        )
+        .eval(&mut env())
+        .unwrap();
+        let env2 = environment(&value);
+        assert!(env2.upgrade().is_some());
+        assert!(matches!(
+            Term::Apply(Box::new(Term::Variable("g".to_string())), vec![]).eval(&mut {
+                let mut env = Environment::new();
+                env.extend("g".to_string(), value);
+                env
+            }),
+            Some(Value::Closure(_))
+        ));
+        assert!(env2.upgrade().is_none());
+
+        let labels = Term::Labels(
+            vec![
+                ("f".to_string(), Box::new(Term::Lambda(vec![], var("g")))),
+                ("g".to_string(), Box::new(Term::Lambda(vec![], var("f")))),
+            ],
+            var("f"),
+        );
+        let value = labels.eval(&mut env()).unwrap();
+        let env3 = environment(&value);
+        drop(value);
+        assert!(env3.upgrade().is_none());
+    }
+
+    #[test]
+    fn interpreter_api() {
+        let mut interpreter = Interpreter::new();
+        assert_eq!(
+            interpreter
+                .eval_str("(apply append (,'(A NIL) ,'(B NIL)))")
+                .map(|value| value.to_string())
+                .ok(),
+            Some("(A (B NIL))".to_string())
+        );
+        assert_eq!(
+            interpreter.eval_str("'A 'B").ok(),
+            Some(Value::Symbol("B".to_string()))
+        );
+        assert_eq!(
+            interpreter.eval_str("").ok(),
+            Some(Value::Symbol("NIL".to_string()))
+        );
+
+        interpreter.define("x", Value::Symbol("X".to_string()));
+        assert_eq!(interpreter.get("x"), Some(Value::Symbol("X".to_string())));
+        assert_eq!(interpreter.get("y"), None);
+        assert_eq!(
+            interpreter
+                .eval_str("(defmacro twice (x) `(cons ,x ,x)) (twice x)")
+                .ok(),
+            Some(dummy_pair("X", "X").eval(&mut env()).unwrap())
+        );
+
+        interpreter.register_builtin("reverse", 1, |args| match &args[0] {
+            Value::Pair(car, cdr) => Ok(Value::Pair(cdr.clone(), car.clone())),
+            value => Err(ErrorKind::Custom(format!("cannot reverse {}", value))),
+        });
+        assert_eq!(
+            interpreter.eval_str("(apply reverse (,'(A B)))").ok(),
+            Some(dummy_pair("B", "A").eval(&mut env()).unwrap())
+        );
+        // NOTE: A builtin is a value like a closure.
+        assert_eq!(
+            interpreter
+                .eval_str("(apply (lambda (f) (apply f (,(cons 'A 'B)))) (,reverse))")
+                .ok(),
+            Some(dummy_pair("B", "A").eval(&mut env()).unwrap())
+        );
+
+        match interpreter.eval_str("(apply reverse (,'A))") {
+            Err(InterpreterError::Eval(err)) => {
+                assert_eq!(err.kind, ErrorKind::Custom("cannot reverse A".to_string()));
+                assert_eq!(err.backtrace[0].name(), "reverse");
+            }
+            result => panic!("A builtin should raise its error, not {:?}", result),
+        }
+        assert!(matches!(
+            interpreter.eval_str("(apply reverse (,'A ,'B))"),
+            Err(InterpreterError::Eval(EvalError {
+                kind: ErrorKind::ArityMismatch {
+                    expected: 1,
+                    found: 2
+                },
+                ..
+            }))
+        ));
+        assert!(matches!(
+            interpreter.eval_str("(cons 'A) (cdr 'A"),
+            Err(InterpreterError::Parse(errors)) if errors.len() == 2
+        ));
+        match interpreter.eval_str("(defmacro twice (x) `(cons ,x ,x)) (twice 'A 'B)") {
+            Err(InterpreterError::MacroExpansion(err)) => assert_eq!(
+                err.to_string(),
+                "macro twice expected 1 argument(s), but found 2"
+            ),
+            result => panic!("A macro call should fail to expand, not {:?}", result),
+        }
+        assert!(matches!(
+            interpreter.eval_file("does-not-exist.lisp"),
+            Err(InterpreterError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn elementary_functions() {
+        let mut interpreter = Interpreter::new();
+        let eval = |interpreter: &mut Interpreter, s: &str| {
+            interpreter
+                .eval_str(s)
+                .map(|value| value.to_string())
+                .map_err(|err| err.to_string())
+        };
+
+        // NOTE: `(maplist xs f)` applies `f` to every element of `xs`.
+        let maplist = "(label maplist (lambda (xs f)
+                         (cond ((atom xs) 'NIL)
+                               ('T (cons (apply f (,(car xs))) (apply maplist (,(cdr xs) ,f)))))))";
+        for (f, expected) in [
+            ("car", "(A (C NIL))"),
+            ("cdr", "(B (D NIL))"),
+            ("atom", "(F (F NIL))"),
+            (
+                "(lambda (x) (apply cons (,(cdr x) ,(car x))))",
+                "((B A) ((D C) NIL))",
+            ),
+        ] {
+            assert_eq!(
+                eval(
+                    &mut interpreter,
+                    &format!("(apply {} (,'((A B) ((C D) NIL)) ,{}))", maplist, f)
+                ),
+                Ok(expected.to_string())
+            );
+        }
+
+        // NOTE: The elementary functions can be returned, and keep their errors.
+        assert_eq!(
+            eval(
+                &mut interpreter,
+                "(apply (apply (lambda () eq) ()) (,'A ,'A))"
+            ),
+            Ok("T".to_string())
+        );
+        assert_eq!(
+            eval(&mut interpreter, "(apply eq (,'(A B) ,'A))"),
+            Err("eq of a non-atom (A B)".to_string())
+        );
+        assert_eq!(
+            eval(&mut interpreter, "(apply car (,'A ,'B))"),
+            Err("expected 1 argument(s), but found 2".to_string())
+        );
+        // NOTE: The special syntax still works.
+        assert_eq!(
+            eval(&mut interpreter, "(car (cdr '(A (B NIL))))"),
+            Ok("B".to_string())
+        );
+    }
+
+    #[test]
+    fn primitives() {
+        let interpreter = Interpreter::new();
+        for primitive in primitive::PRIMITIVES {
+            assert!(
+                matches!(interpreter.get(primitive.name), Some(Value::Closure(_))),
+                "{}",
+                primitive.name
+            );
+        }
+
+        // NOTE: The name of a primitive is an identifier, which may be bound again.
+        assert_eq!(
+            Interpreter::new()
+                .eval_str("(apply (lambda (car) (car car)) (,'(A B)))")
+                .ok(),
+            Some(Value::Symbol("A".to_string()))
+        );
+        assert_eq!(
+            primitive("cons", vec![dummy_atom("A")]).eval_with(&mut env(), &mut Context::new()),
+            Err(EvalError {
+                kind: ErrorKind::ArityMismatch {
+                    expected: 2,
+                    found: 1
+                },
+                backtrace: vec![],
+            })
+        );
+    }
 }
@@ -1,12 +1,23 @@
 mod sample_based_tests {
+    use std::io::{self, Read};
+
     use pure_lisp::{
-        lexer::Lexer,
+        error::{ParseError, ParseErrorKind},
+        integer::Integer,
+        lexer::{CaseMode, Lexer},
         parser::Parser,
-        syntax::{SExpression, Term},
+        primitive,
+        syntax::{SExpression, Span, Term},
     };
 
-    fn create_parser(s: &str) -> Parser {
-        Parser::new(Lexer::new(&s.to_string()))
+    fn create_parser(s: &str) -> Parser<'_> {
+        Parser::new(Lexer::new(s))
+    }
+
+    // NOTE: The arguments are boxed as in `Term::Primitive`.
+    #[allow(clippy::vec_box)]
+    fn primitive(name: &str, terms: Vec<Box<Term>>) -> Term {
+        Term::Primitive(primitive::lookup(name).unwrap(), terms)
     }
 
     #[test]
@@ -15,7 +26,10 @@ mod sample_based_tests {
             create_parser("(cond ((atom x) x) (t y))").parse().unwrap(),
             Term::Cond(vec![
                 (
-                    Box::new(Term::Atom(Box::new(Term::Variable("x".to_string())))),
+                    Box::new(primitive(
+                        "atom",
+                        vec![Box::new(Term::Variable("x".to_string()))]
+                    )),
                     Box::new(Term::Variable("x".to_string()))
                 ),
                 (
@@ -28,11 +42,70 @@ mod sample_based_tests {
         assert_eq!(create_parser("(cond)").parse().unwrap(), Term::Cond(vec![]));
     }
 
+    #[test]
+    fn and_or() {
+        assert_eq!(
+            create_parser("(and x (atom y))").parse().unwrap(),
+            Term::And(vec![
+                Box::new(Term::Variable("x".to_string())),
+                Box::new(primitive(
+                    "atom",
+                    vec![Box::new(Term::Variable("y".to_string()))]
+                ))
+            ])
+        );
+
+        assert_eq!(create_parser("(or)").parse().unwrap(), Term::Or(vec![]));
+
+        // NOTE: Elsewhere than at the head of a list, the names stand for the functions of the prelude.
+        assert_eq!(
+            create_parser("(apply and (,x ,y))").parse().unwrap(),
+            Term::Apply(
+                Box::new(Term::Variable("and".to_string())),
+                vec![
+                    Box::new(Term::Variable("x".to_string())),
+                    Box::new(Term::Variable("y".to_string()))
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn not() {
+        assert_eq!(
+            create_parser("(not x)").parse().unwrap(),
+            Term::Not(Box::new(Term::Variable("x".to_string())))
+        )
+    }
+
+    #[test]
+    fn if_() {
+        assert_eq!(
+            create_parser("(if x y z)").parse().unwrap(),
+            Term::If(
+                Box::new(Term::Variable("x".to_string())),
+                Box::new(Term::Variable("y".to_string())),
+                Some(Box::new(Term::Variable("z".to_string())))
+            )
+        );
+
+        assert_eq!(
+            create_parser("(if x y)").parse().unwrap(),
+            Term::If(
+                Box::new(Term::Variable("x".to_string())),
+                Box::new(Term::Variable("y".to_string())),
+                None
+            )
+        );
+
+        assert!(create_parser("(if x)").parse().is_none());
+    }
+
     #[test]
     fn atom() {
         assert_eq!(
             create_parser("(atom x)").parse().unwrap(),
-            Term::Atom(Box::new(Term::Variable("x".to_string())))
+            primitive("atom", vec![Box::new(Term::Variable("x".to_string()))])
         )
     }
 
@@ -40,9 +113,12 @@ mod sample_based_tests {
     fn eq() {
         assert_eq!(
             create_parser("(eq x y)").parse().unwrap(),
-            Term::Eq(
-                Box::new(Term::Variable("x".to_string())),
-                Box::new(Term::Variable("y".to_string()))
+            primitive(
+                "eq",
+                vec![
+                    Box::new(Term::Variable("x".to_string())),
+                    Box::new(Term::Variable("y".to_string()))
+                ]
             )
         )
     }
@@ -51,7 +127,7 @@ mod sample_based_tests {
     fn car() {
         assert_eq!(
             create_parser("(car x)").parse().unwrap(),
-            Term::Car(Box::new(Term::Variable("x".to_string())))
+            primitive("car", vec![Box::new(Term::Variable("x".to_string()))])
         )
     }
 
@@ -59,7 +135,7 @@ mod sample_based_tests {
     fn cdr() {
         assert_eq!(
             create_parser("(cdr x)").parse().unwrap(),
-            Term::Cdr(Box::new(Term::Variable("x".to_string())))
+            primitive("cdr", vec![Box::new(Term::Variable("x".to_string()))])
         )
     }
 
@@ -67,19 +143,191 @@ mod sample_based_tests {
     fn cons() {
         assert_eq!(
             create_parser("(cons x y)").parse().unwrap(),
-            Term::Cons(
-                Box::new(Term::Variable("x".to_string())),
-                Box::new(Term::Variable("y".to_string()))
+            primitive(
+                "cons",
+                vec![
+                    Box::new(Term::Variable("x".to_string())),
+                    Box::new(Term::Variable("y".to_string()))
+                ]
             )
         )
     }
 
+    #[test]
+    fn accessor() {
+        assert_eq!(
+            create_parser("(caddr x)").parse().unwrap(),
+            primitive("caddr", vec![Box::new(Term::Variable("x".to_string()))])
+        );
+        assert_eq!(create_parser("(cadr x y)").parse(), None);
+        // NOTE: Outside the head of a form, an accessor is just an identifier.
+        assert_eq!(
+            create_parser("cadr").parse().unwrap(),
+            Term::Variable("cadr".to_string())
+        );
+    }
+
+    #[test]
+    fn integer() {
+        assert_eq!(
+            create_parser("-12").parse().unwrap(),
+            Term::Quote(SExpression::Integer(Integer::from(-12)))
+        );
+        assert_eq!(
+            create_parser("'(1 X)").parse().unwrap(),
+            Term::Quote(SExpression::Pair(
+                Box::new(SExpression::Integer(Integer::from(1))),
+                Box::new(SExpression::Symbol("X".to_string()))
+            ))
+        );
+        // NOTE: A sign alone is the name of a primitive.
+        assert_eq!(
+            create_parser("(- 1 +2)").parse().unwrap(),
+            primitive(
+                "-",
+                vec![
+                    Box::new(Term::Quote(SExpression::Integer(Integer::from(1)))),
+                    Box::new(Term::Quote(SExpression::Integer(Integer::from(2))))
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn string() {
+        assert_eq!(
+            create_parser(r#""a \"b\"\n\\""#).parse().unwrap(),
+            Term::Quote(SExpression::String("a \"b\"\n\\".to_string()))
+        );
+        assert_eq!(
+            create_parser(r#"'("x" NIL)"#).parse().unwrap(),
+            Term::Quote(SExpression::Pair(
+                Box::new(SExpression::String("x".to_string())),
+                Box::new(SExpression::Symbol("NIL".to_string()))
+            ))
+        );
+        assert_eq!(
+            create_parser(r#""\q""#)
+                .next_term()
+                .unwrap()
+                .map_err(|err| err.kind),
+            Err(ParseErrorKind::UnexpectedToken(r#""\q""#.to_string()))
+        );
+        assert_eq!(
+            create_parser(r#""abc"#)
+                .next_term()
+                .unwrap()
+                .map_err(|err| err.kind),
+            Err(ParseErrorKind::UnexpectedToken(r#""abc"#.to_string()))
+        );
+    }
+
+    #[test]
+    fn identifiers() {
+        for (source, name) in [
+            ("last-elem", "last-elem"),
+            ("null?", "null?"),
+            ("*env*", "*env*"),
+            ("1+", "1+"),
+            ("λ", "λ"),
+            ("-", "-"),
+            ("|hello world|", "hello world"),
+            ("|-5|", "-5"),
+            (r"|a\|b|", "a|b"),
+            ("ab|c d|e", "abc de"),
+            // NOTE: A keyword between bars is an identifier.
+            ("|lambda|", "lambda"),
+        ] {
+            assert_eq!(
+                create_parser(source).parse(),
+                Some(Term::Variable(name.to_string())),
+                "{}",
+                source
+            );
+        }
+        assert_eq!(
+            create_parser("'(lambda |a b|)").parse().unwrap(),
+            Term::Quote(SExpression::Pair(
+                Box::new(SExpression::Symbol("lambda".to_string())),
+                Box::new(SExpression::Symbol("a b".to_string()))
+            ))
+        );
+    }
+
+    #[test]
+    fn case_modes() {
+        let parse =
+            |source: &str, case_mode| Parser::new(Lexer::with_case_mode(source, case_mode)).parse();
+
+        for (source, upcase, downcase) in [
+            ("x", "X", "x"),
+            ("Null?", "NULL?", "null?"),
+            ("λ", "Λ", "λ"),
+            ("1+", "1+", "1+"),
+            // NOTE: The letters between bars are read as written.
+            ("|aB|", "aB", "aB"),
+            ("a|b|C", "AbC", "abc"),
+            // NOTE: The atoms of the interpreter are read from their names in the mode.
+            ("nil", "NIL", "NIL"),
+            ("T", "T", "T"),
+            ("|nil|", "nil", "nil"),
+        ] {
+            assert_eq!(
+                parse(source, CaseMode::Upcase),
+                Some(Term::Variable(upcase.to_string())),
+                "{}",
+                source
+            );
+            assert_eq!(
+                parse(source, CaseMode::Downcase),
+                Some(Term::Variable(downcase.to_string())),
+                "{}",
+                source
+            );
+        }
+
+        // NOTE: Keywords and primitives are told apart regardless of case unless it is preserved.
+        assert_eq!(
+            parse("(LAMBDA (X) (Car x))", CaseMode::Upcase),
+            Some(Term::Lambda(
+                vec!["X".to_string()],
+                Box::new(primitive(
+                    "car",
+                    vec![Box::new(Term::Variable("X".to_string()))]
+                ))
+            ))
+        );
+        assert_eq!(
+            parse("'Lambda", CaseMode::Downcase),
+            Some(Term::Quote(SExpression::Symbol("lambda".to_string())))
+        );
+        assert_eq!(
+            parse("(LAMBDA)", CaseMode::Preserve),
+            Some(Term::MacroCall("LAMBDA".to_string(), vec![]))
+        );
+        assert_eq!(
+            parse("'Abc", CaseMode::Preserve),
+            Some(Term::Quote(SExpression::Symbol("Abc".to_string())))
+        );
+    }
+
     #[test]
     fn variable() {
         assert_eq!(
             create_parser("x").parse().unwrap(),
             Term::Variable("x".to_string())
-        )
+        );
+        // NOTE: An elementary function outside the head of a form is a variable.
+        assert_eq!(
+            create_parser("(apply f (,car ,cons))").parse().unwrap(),
+            Term::Apply(
+                Box::new(Term::Variable("f".to_string())),
+                vec![
+                    Box::new(Term::Variable("car".to_string())),
+                    Box::new(Term::Variable("cons".to_string()))
+                ]
+            )
+        );
     }
 
     #[test]
@@ -134,6 +382,208 @@ mod sample_based_tests {
         )
     }
 
+    #[test]
+    fn labels() {
+        assert_eq!(
+            create_parser("(labels ((f x) (g y)) z)").parse().unwrap(),
+            Term::Labels(
+                vec![
+                    ("f".to_string(), Box::new(Term::Variable("x".to_string()))),
+                    ("g".to_string(), Box::new(Term::Variable("y".to_string())))
+                ],
+                Box::new(Term::Variable("z".to_string()))
+            )
+        );
+
+        assert_eq!(
+            create_parser("(labels () x)").parse().unwrap(),
+            Term::Labels(vec![], Box::new(Term::Variable("x".to_string())))
+        );
+    }
+
+    #[test]
+    fn spans() {
+        let term = create_parser("(cons x\n  (car y))").parse().unwrap();
+        assert_eq!(
+            term.span(),
+            Some(Span {
+                start: 0,
+                end: 18,
+                line: 1,
+                column: 1
+            })
+        );
+
+        let Term::Primitive(_, terms) = term.node() else {
+            panic!("{:?} should be a cons", term);
+        };
+        let [term1, term2] = terms.as_slice() else {
+            panic!("{:?} should have two arguments", term);
+        };
+        assert_eq!(
+            term1.span(),
+            Some(Span {
+                start: 6,
+                end: 7,
+                line: 1,
+                column: 7
+            })
+        );
+        assert_eq!(
+            term2.span(),
+            Some(Span {
+                start: 10,
+                end: 17,
+                line: 2,
+                column: 3
+            })
+        );
+
+        // NOTE: Spans are not compared.
+        assert_eq!(
+            *term2,
+            Box::new(primitive(
+                "car",
+                vec![Box::new(Term::Variable("y".to_string()))]
+            ))
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let error = |kind: ParseErrorKind, start: usize, end: usize, line: usize, column: usize| {
+            ParseError {
+                kind,
+                span: Span {
+                    start,
+                    end,
+                    line,
+                    column,
+                },
+            }
+        };
+        let unexpected = |word: &str| ParseErrorKind::UnexpectedToken(word.to_string());
+
+        assert_eq!(create_parser(" ").next_term(), None);
+        assert_eq!(
+            create_parser("(car x y)").next_term(),
+            Some(Err(error(unexpected("y"), 7, 8, 1, 8)))
+        );
+        assert_eq!(
+            create_parser("(car x").next_term(),
+            Some(Err(error(ParseErrorKind::UnexpectedEnd, 6, 6, 1, 7)))
+        );
+
+        // NOTE: Parsing goes on after the end of each malformed term.
+        assert_eq!(
+            create_parser("(car x y) x\n) (cdr (lambda x))\n(cons x y z\n(atom y)").parse_all(),
+            (
+                vec![
+                    Term::Variable("x".to_string()),
+                    primitive("atom", vec![Box::new(Term::Variable("y".to_string()))])
+                ],
+                vec![
+                    error(unexpected("y"), 7, 8, 1, 8),
+                    error(unexpected(")"), 12, 13, 2, 1),
+                    error(unexpected("x"), 27, 28, 2, 16),
+                    error(unexpected("z"), 41, 42, 3, 11)
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn streams() {
+        let reader = "(cons 'A\n  'B)\n\"two\nlines\" |a\nb|\n(car)\n'C".as_bytes();
+        let parser = Parser::new(Lexer::from_reader(reader, CaseMode::Preserve));
+
+        assert_eq!(
+            parser.collect::<Vec<_>>(),
+            vec![
+                Ok(primitive(
+                    "cons",
+                    vec![
+                        Box::new(Term::Quote(SExpression::Symbol("A".to_string()))),
+                        Box::new(Term::Quote(SExpression::Symbol("B".to_string())))
+                    ]
+                )),
+                Ok(Term::Quote(SExpression::String("two\nlines".to_string()))),
+                Ok(Term::Variable("a\nb".to_string())),
+                Err(ParseError {
+                    kind: ParseErrorKind::UnexpectedToken(")".to_string()),
+                    span: Span {
+                        start: 37,
+                        end: 38,
+                        line: 6,
+                        column: 5
+                    }
+                }),
+                Ok(Term::Quote(SExpression::Symbol("C".to_string()))),
+            ]
+        );
+
+        struct Broken;
+
+        impl io::Read for Broken {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("broken pipe"))
+            }
+        }
+
+        // NOTE: A term is parsed before the reader is asked for the lines after it.
+        let reader = "'A\n".as_bytes().chain(io::BufReader::new(Broken));
+        let mut parser = Parser::new(Lexer::from_reader(reader, CaseMode::Preserve));
+        assert_eq!(
+            parser.next(),
+            Some(Ok(Term::Quote(SExpression::Symbol("A".to_string()))))
+        );
+        assert_eq!(
+            parser.next().map(|result| result.map_err(|err| err.kind)),
+            Some(Err(ParseErrorKind::Io("broken pipe".to_string())))
+        );
+        assert_eq!(parser.next(), None);
+    }
+
+    #[test]
+    fn unterminated() {
+        // NOTE: An unterminated string is the rest of its line, and the next lines are parsed again.
+        let reader = "\"one\ntwo\n'B".as_bytes();
+        let parser = Parser::new(Lexer::from_reader(reader, CaseMode::Preserve));
+        assert_eq!(
+            parser
+                .map(|result| result.map_err(|err| err.kind))
+                .collect::<Vec<_>>(),
+            vec![
+                Err(ParseErrorKind::UnexpectedToken("\"one".to_string())),
+                Ok(Term::Variable("two".to_string())),
+                Ok(Term::Quote(SExpression::Symbol("B".to_string()))),
+            ]
+        );
+
+        // NOTE: The end of a string is not looked for in the whole rest of the input.
+        let reader = "\"one"
+            .as_bytes()
+            .chain(io::BufReader::new(io::repeat(b'\n')));
+        let mut parser = Parser::new(Lexer::from_reader(reader, CaseMode::Preserve));
+        assert_eq!(
+            parser.next().map(|result| result.map_err(|err| err.kind)),
+            Some(Err(ParseErrorKind::UnexpectedToken("\"one".to_string())))
+        );
+    }
+
+    #[test]
+    fn trace() {
+        assert_eq!(
+            create_parser("(trace f)").parse().unwrap(),
+            Term::Trace("f".to_string())
+        );
+
+        assert_eq!(
+            create_parser("(untrace f)").parse().unwrap(),
+            Term::Untrace("f".to_string())
+        );
+    }
+
     #[test]
     fn quote() {
         assert_eq!(
@@ -149,4 +599,61 @@ mod sample_based_tests {
             ))
         );
     }
+
+    #[test]
+    fn quasiquote() {
+        assert_eq!(
+            create_parser("`(car ,x)").parse().unwrap(),
+            Term::Quasiquote(Box::new(primitive(
+                "car",
+                vec![Box::new(Term::Unquote(Box::new(Term::Variable(
+                    "x".to_string()
+                ))))]
+            )))
+        );
+
+        assert_eq!(
+            create_parser("`(apply f (,,x ,@y))").parse().unwrap(),
+            Term::Quasiquote(Box::new(Term::Apply(
+                Box::new(Term::Variable("f".to_string())),
+                vec![
+                    Box::new(Term::Unquote(Box::new(Term::Variable("x".to_string())))),
+                    Box::new(Term::UnquoteSplicing(Box::new(Term::Variable(
+                        "y".to_string()
+                    ))))
+                ]
+            )))
+        );
+    }
+
+    #[test]
+    fn defmacro() {
+        assert_eq!(
+            create_parser("(defmacro m (x y) x)").parse().unwrap(),
+            Term::Defmacro(
+                "m".to_string(),
+                vec!["x".to_string(), "y".to_string()],
+                Box::new(Term::Variable("x".to_string()))
+            )
+        );
+
+        assert_eq!(
+            create_parser("(m x (car y))").parse().unwrap(),
+            Term::MacroCall(
+                "m".to_string(),
+                vec![
+                    Box::new(Term::Variable("x".to_string())),
+                    Box::new(primitive(
+                        "car",
+                        vec![Box::new(Term::Variable("y".to_string()))]
+                    ))
+                ]
+            )
+        );
+
+        assert_eq!(
+            create_parser("(m)").parse().unwrap(),
+            Term::MacroCall("m".to_string(), vec![])
+        );
+    }
 }
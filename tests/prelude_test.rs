@@ -0,0 +1,155 @@
+mod sample_based_tests {
+    use pure_lisp::{
+        environment::Environment,
+        interpreter::{BooleanMode, Context},
+        lexer::Lexer,
+        parser::Parser,
+        prelude,
+        syntax::Value,
+    };
+
+    fn eval(s: &str) -> Option<Value> {
+        let mut env = Environment::new();
+        prelude::load(&mut env);
+        Parser::new(Lexer::new(s)).parse()?.eval(&mut env)
+    }
+
+    fn value(s: &str) -> Option<Value> {
+        eval(&format!("'{}", s))
+    }
+
+    // NOTE: The examples are from the paper, where `(A, B)` is written `(A (B NIL))`
+    // and `(A . B)` is written `(A B)`.
+
+    #[test]
+    fn ff() {
+        assert_eq!(eval("(apply ff (,'((A B) C)))"), value("A"));
+    }
+
+    #[test]
+    fn subst() {
+        assert_eq!(
+            eval("(apply subst (,'(X A) ,'B ,'((A B) C)))"),
+            value("((A (X A)) C)")
+        );
+    }
+
+    #[test]
+    fn equal() {
+        assert_eq!(
+            eval("(apply equal (,'(A (B NIL)) ,'(A (B NIL))))"),
+            value("T")
+        );
+        assert_eq!(eval("(apply equal (,'(A (B NIL)) ,'(A B)))"), value("F"));
+        assert_eq!(eval("(apply equal (,'A ,'(A B)))"), value("F"));
+    }
+
+    #[test]
+    fn null() {
+        assert_eq!(eval("(apply null (,'NIL))"), value("T"));
+        assert_eq!(eval("(apply null (,'A))"), value("F"));
+        assert_eq!(eval("(apply null (,'(A NIL)))"), value("F"));
+    }
+
+    #[test]
+    fn propositional_connectives() {
+        assert_eq!(eval("(apply and (,'T ,'T))"), value("T"));
+        assert_eq!(eval("(apply and (,'T ,'F))"), value("F"));
+        assert_eq!(eval("(apply or (,'F ,'T))"), value("T"));
+        assert_eq!(eval("(apply or (,'F ,'F))"), value("F"));
+        assert_eq!(eval("(apply not (,'F))"), value("T"));
+        assert_eq!(eval("(apply not (,'T))"), value("F"));
+    }
+
+    #[test]
+    fn boolean_modes() {
+        for boolean_mode in [
+            BooleanMode::Standard,
+            BooleanMode::Strict,
+            BooleanMode::Lisp15,
+        ] {
+            // NOTE: `(eq 'A 'A)` and `(eq 'A 'B)` are the truth values of the mode.
+            let holds = |s: &str| {
+                let mut env = Environment::new();
+                prelude::load(&mut env);
+                let mut ctx = Context::new();
+                ctx.boolean_mode = boolean_mode;
+                Parser::new(Lexer::new(&format!("(if {} 'YES 'NO)", s)))
+                    .parse()?
+                    .eval_with(&mut env, &mut ctx)
+                    .ok()
+            };
+
+            for (s, expected) in [
+                ("(apply equal (,'(A NIL) ,'(A NIL)))", "YES"),
+                ("(apply equal (,'(A NIL) ,'B))", "NO"),
+                ("(apply equal (,'A ,'(A NIL)))", "NO"),
+                ("(apply among (,'A ,'(B (A NIL))))", "YES"),
+                ("(apply among (,'A ,'(B NIL)))", "NO"),
+                ("(apply null (,'NIL))", "YES"),
+                ("(apply null (,'(A NIL)))", "NO"),
+                ("(apply not (,(eq 'A 'B)))", "YES"),
+                ("(apply not (,(eq 'A 'A)))", "NO"),
+                ("(apply and (,(eq 'A 'A) ,(eq 'A 'A)))", "YES"),
+                ("(apply and (,(eq 'A 'A) ,(eq 'A 'B)))", "NO"),
+                ("(apply or (,(eq 'A 'B) ,(eq 'A 'A)))", "YES"),
+                ("(apply or (,(eq 'A 'B) ,(eq 'A 'B)))", "NO"),
+            ] {
+                assert_eq!(holds(s), value(expected), "{} in {:?}", s, boolean_mode);
+            }
+        }
+    }
+
+    #[test]
+    fn connectives_and_forms() {
+        // NOTE: The form stops at the first false argument, while the function evaluates them all.
+        assert_eq!(eval("(and 'F (car 'A))"), value("F"));
+        assert_eq!(eval("(apply and (,'F ,(car 'A)))"), None);
+        assert_eq!(eval("(or 'T (car 'A))"), value("T"));
+        assert_eq!(eval("(apply or (,'T ,(car 'A)))"), None);
+        assert_eq!(eval("(not 'A)"), value("T"));
+        assert_eq!(eval("(apply not (,'A))"), value("T"));
+    }
+
+    #[test]
+    fn append() {
+        assert_eq!(
+            eval("(apply append (,'(A (B NIL)) ,'(C (D (E NIL)))))"),
+            value("(A (B (C (D (E NIL)))))")
+        );
+    }
+
+    #[test]
+    fn among() {
+        assert_eq!(
+            eval("(apply among (,'(B NIL) ,'(A ((B NIL) NIL))))"),
+            value("T")
+        );
+        assert_eq!(eval("(apply among (,'C ,'(A (B NIL))))"), value("F"));
+    }
+
+    #[test]
+    fn pair() {
+        assert_eq!(
+            eval("(apply pair (,'(A (B (C NIL))) ,'(X ((Y (Z NIL)) (U NIL)))))"),
+            value("((A (X NIL)) ((B ((Y (Z NIL)) NIL)) ((C (U NIL)) NIL)))")
+        );
+        assert_eq!(eval("(apply pair (,'(A NIL) ,'NIL))"), None);
+    }
+
+    #[test]
+    fn assoc() {
+        assert_eq!(
+            eval("(apply assoc (,'X ,'((W ((A (B NIL)) NIL)) ((X ((C (D NIL)) NIL)) ((Y ((E (F NIL)) NIL)) NIL)))))"),
+            value("(C (D NIL))")
+        );
+    }
+
+    #[test]
+    fn sublis() {
+        assert_eq!(
+            eval("(apply sublis (,'((X ((A (B NIL)) NIL)) ((Y ((B (C NIL)) NIL)) NIL)) ,'(A (X Y))))"),
+            value("(A ((A (B NIL)) (B (C NIL))))")
+        );
+    }
+}
@@ -0,0 +1,115 @@
+mod sample_based_tests {
+    use pure_lisp::{lexer::Lexer, parser::Parser, prelude, termination};
+
+    /// The functions defined in `s`, with whether they are proven to terminate.
+    fn check(s: &str) -> Vec<(String, bool)> {
+        let term = Parser::new(Lexer::new(s)).parse().unwrap();
+        termination::check(&term)
+            .into_iter()
+            .map(|verdict| (verdict.name, verdict.proven))
+            .collect()
+    }
+
+    #[test]
+    fn structural_recursion() {
+        assert_eq!(
+            check("(label f (lambda (x) (cond ((atom x) x) ('T (apply f (,(car (cdr x))))))))"),
+            vec![("f".to_string(), true)]
+        );
+        assert_eq!(
+            check(
+                "(label f (lambda (x y) (if (not (apply null (,y))) (apply f (,x ,(cdr y))) x)))"
+            ),
+            vec![("f".to_string(), true)]
+        );
+        assert_eq!(
+            check("(label f (lambda (x) (cond ((atom x) x) ('T (apply f (,(cddr x)))))))"),
+            vec![("f".to_string(), true)]
+        );
+        assert_eq!(
+            check("(label f (lambda (x) (and (not (eq x 'NIL)) (apply f (,(cdr x))))))"),
+            vec![("f".to_string(), true)]
+        );
+        // NOTE: A function without recursive calls terminates.
+        assert_eq!(
+            check("(label f (lambda (x) x))"),
+            vec![("f".to_string(), true)]
+        );
+    }
+
+    #[test]
+    fn unproven() {
+        // NOTE: The argument does not get smaller.
+        assert_eq!(
+            check("(label f (lambda (x) (cond ((atom x) x) ('T (apply f (,x))))))"),
+            vec![("f".to_string(), false)]
+        );
+        // NOTE: The parameter is not tested.
+        assert_eq!(
+            check("(label f (lambda (x) (apply f (,(cdr x)))))"),
+            vec![("f".to_string(), false)]
+        );
+        // NOTE: The parameter is tested the wrong way around.
+        assert_eq!(
+            check("(label f (lambda (x) (cond ((atom x) (apply f (,(cdr x)))) ('T x))))"),
+            vec![("f".to_string(), false)]
+        );
+        // NOTE: The arguments get smaller at different positions.
+        assert_eq!(
+            check(
+                "(label f (lambda (x y)
+                   (cond ((atom x) x)
+                         ((atom y) y)
+                         ((atom (car x)) (apply f (,(cdr x) ,(cons y y))))
+                         ('T (apply f (,(cons x x) ,(cdr y)))))))"
+            ),
+            vec![("f".to_string(), false)]
+        );
+        // NOTE: The function may be applied to anything by another one.
+        assert_eq!(
+            check("(label f (lambda (g x) (cond ((atom x) x) ('T (apply g (,f ,(cdr x)))))))"),
+            vec![("f".to_string(), false)]
+        );
+    }
+
+    #[test]
+    fn mutual_recursion() {
+        assert_eq!(
+            check(
+                "(labels ((ev (lambda (x) (cond ((atom x) 'T) ('T (apply od (,(cdr x)))))))
+                          (od (lambda (x) (cond ((atom x) 'F) ('T (apply ev (,(cdr x))))))))
+                   ev)"
+            ),
+            vec![("ev".to_string(), true), ("od".to_string(), true)]
+        );
+        assert_eq!(
+            check(
+                "(labels ((f (lambda (x) (apply g (,x))))
+                          (g (lambda (x) (cond ((atom x) x) ('T (apply g (,(cdr x))))))))
+                   f)"
+            ),
+            vec![("f".to_string(), true), ("g".to_string(), true)]
+        );
+        assert_eq!(
+            check(
+                "(labels ((f (lambda (x) (cond ((atom x) x) ('T (apply g (,(cdr x)))))))
+                          (g (lambda (x) (apply f (,(cons x x))))))
+                   f)"
+            ),
+            vec![("f".to_string(), false), ("g".to_string(), false)]
+        );
+    }
+
+    #[test]
+    fn prelude() {
+        let terms = Parser::new(Lexer::new(prelude::SOURCE)).parse_all().0;
+        let verdicts = terms
+            .iter()
+            .flat_map(termination::check)
+            .filter(|verdict| !verdict.proven)
+            .map(|verdict| verdict.name)
+            .collect::<Vec<_>>();
+        // NOTE: `assoc` does not test its list before it takes it apart.
+        assert_eq!(verdicts, vec!["assoc".to_string()]);
+    }
+}
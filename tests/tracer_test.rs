@@ -0,0 +1,160 @@
+mod sample_based_tests {
+    use pure_lisp::{
+        environment::Environment,
+        error::EvalError,
+        interpreter::Context,
+        lexer::{CaseMode, Lexer},
+        parser::Parser,
+        syntax::{Term, Value},
+        tracer::{PrintTracer, Tracer},
+    };
+    use std::{cell::RefCell, io, rc::Rc};
+
+    fn parse(s: &str) -> Term {
+        Parser::new(Lexer::new(s)).parse().unwrap()
+    }
+
+    fn last() -> &'static str {
+        "(label last (lambda (xs) (cond ((atom (cdr xs)) (car xs)) ('T (apply last (,(cdr xs)))))))"
+    }
+
+    #[derive(Clone, Default)]
+    struct Buffer(Rc<RefCell<Vec<u8>>>);
+
+    impl io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct Events(Rc<RefCell<Vec<(usize, String, String)>>>);
+
+    impl Tracer for Events {
+        fn enter(&mut self, depth: usize, name: &str, args: &[Value]) {
+            let args = args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+            self.0
+                .borrow_mut()
+                .push((depth, name.to_string(), args.join(" ")));
+        }
+
+        fn exit(&mut self, depth: usize, name: &str, result: &Result<Value, EvalError>) {
+            let result = match result {
+                Ok(value) => value.to_string(),
+                Err(err) => err.to_string(),
+            };
+            self.0
+                .borrow_mut()
+                .push((depth, name.to_string(), format!("=> {}", result)));
+        }
+    }
+
+    #[test]
+    fn print_tracer() {
+        let buffer = Buffer::default();
+        let mut ctx = Context::new();
+        ctx.tracer = Some(Box::new(PrintTracer(buffer.clone(), CaseMode::Preserve)));
+        let mut env = Environment::new();
+
+        parse("(trace last)").eval_with(&mut env, &mut ctx).unwrap();
+        parse(&format!("(apply {} (,'(X (Y (Z NIL)))))", last()))
+            .eval_with(&mut env, &mut ctx)
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(buffer.0.borrow().clone()).unwrap(),
+            [
+                "(last (X (Y (Z NIL))))",
+                "  (last (Y (Z NIL)))",
+                "    (last (Z NIL))",
+                "    last returned Z",
+                "  last returned Z",
+                "last returned Z",
+                ""
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn print_tracer_case_mode() {
+        let buffer = Buffer::default();
+        let mut ctx = Context::new();
+        ctx.tracer = Some(Box::new(PrintTracer(buffer.clone(), CaseMode::Downcase)));
+        let mut env = Environment::new();
+        let parse = |s: &str| {
+            Parser::new(Lexer::with_case_mode(s, CaseMode::Downcase))
+                .parse()
+                .unwrap()
+        };
+
+        parse("(trace last)").eval_with(&mut env, &mut ctx).unwrap();
+        parse(&format!("(apply {} (,'(x (|Y| nil))))", last()))
+            .eval_with(&mut env, &mut ctx)
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(buffer.0.borrow().clone()).unwrap(),
+            [
+                "(last (x (|Y| nil)))",
+                "  (last (|Y| nil))",
+                "  last returned |Y|",
+                "last returned |Y|",
+                ""
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn trace_untrace() {
+        let events = Events::default();
+        let mut ctx = Context::new();
+        ctx.tracer = Some(Box::new(events.clone()));
+        let mut env = Environment::new();
+        let mut eval = |s: &str| parse(s).eval_with(&mut env, &mut ctx);
+
+        // NOTE: Nothing is reported until a function is traced.
+        eval(&format!("(apply {} (,'(X NIL)))", last())).unwrap();
+        assert!(events.0.borrow().is_empty());
+
+        eval("(trace f)").unwrap();
+        eval("(apply (lambda (f) (apply f (,'A))) (,(lambda (x) (car x))))").unwrap_err();
+        assert_eq!(
+            *events.0.borrow(),
+            vec![
+                (0, "f".to_string(), "A".to_string()),
+                (0, "f".to_string(), "=> car of an atom A".to_string())
+            ]
+        );
+
+        eval("(untrace f)").unwrap();
+        eval("(apply (lambda (f) (apply f (,'A))) (,(lambda (x) x)))").unwrap();
+        assert_eq!(events.0.borrow().len(), 2);
+    }
+
+    #[test]
+    fn trace_all() {
+        let events = Events::default();
+        let mut ctx = Context::new();
+        ctx.tracer = Some(Box::new(events.clone()));
+        ctx.trace_all = true;
+
+        parse("(apply (lambda (x) (apply (lambda (y) y) (,x))) (,'A))")
+            .eval_with(&mut Environment::new(), &mut ctx)
+            .unwrap();
+        assert_eq!(
+            *events.0.borrow(),
+            vec![
+                (0, "<lambda>".to_string(), "A".to_string()),
+                (1, "<lambda>".to_string(), "A".to_string()),
+                (1, "<lambda>".to_string(), "=> A".to_string()),
+                (0, "<lambda>".to_string(), "=> A".to_string())
+            ]
+        );
+    }
+}
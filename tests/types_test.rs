@@ -0,0 +1,150 @@
+mod sample_based_tests {
+    use pure_lisp::{
+        environment::Environment,
+        error::TypeErrorKind,
+        lexer::Lexer,
+        parser::Parser,
+        prelude,
+        types::{Type, TypeChecker},
+    };
+
+    fn infer(types: &TypeChecker, s: &str) -> Result<String, (TypeErrorKind, String)> {
+        let term = Parser::new(Lexer::new(s)).parse().unwrap();
+        types
+            .infer(&term)
+            .map(|ty| ty.to_string())
+            .map_err(|err| (err.kind, err.span.unwrap().to_string()))
+    }
+
+    fn types() -> TypeChecker {
+        TypeChecker::new(&Environment::new())
+    }
+
+    #[test]
+    fn values() {
+        assert_eq!(infer(&types(), "'A"), Ok("Atom".to_string()));
+        assert_eq!(infer(&types(), "'NIL"), Ok("List<a>".to_string()));
+        assert_eq!(infer(&types(), "(atom 'A)"), Ok("Bool".to_string()));
+        assert_eq!(
+            infer(&types(), "(cons 'A 'B)"),
+            Ok("Pair<Atom, Atom>".to_string())
+        );
+        assert_eq!(
+            infer(&types(), "(lambda (n) (if (< n 0) (- 0 n) n))"),
+            Ok("Integer -> Integer".to_string())
+        );
+        assert_eq!(
+            infer(
+                &types(),
+                r#"(lambda (x) (string->symbol (string-append (symbol->string x) "!")))"#
+            ),
+            Ok("Symbol -> Symbol".to_string())
+        );
+        assert_eq!(
+            infer(&types(), "(explode 'AB)"),
+            Ok("List<Symbol>".to_string())
+        );
+        assert_eq!(
+            infer(&types(), "(lambda (x) (cadr x))"),
+            Ok("Pair<a, Pair<b, c>> -> b".to_string())
+        );
+        assert_eq!(
+            infer(&types(), "(lambda (f x) (apply f (,(apply f (,x)))))"),
+            Ok("(a -> a, a) -> a".to_string())
+        );
+        assert_eq!(
+            infer(
+                &types(),
+                "(label last (lambda (x) (cond ((atom (cdr x)) (car x)) ('T (apply last (,(cdr x)))))))"
+            ),
+            Ok("List<a> -> a".to_string())
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            infer(&types(), "(cons 'A (car 'B))"),
+            Err((
+                TypeErrorKind::Mismatch(
+                    Type::Pair(Box::new(Type::Var(0)), Box::new(Type::Var(1))),
+                    Type::Atom
+                ),
+                "1:10".to_string()
+            ))
+        );
+        assert_eq!(
+            infer(&types(), "(eq '(A B) 'A)"),
+            Err((
+                TypeErrorKind::Mismatch(
+                    Type::Atom,
+                    Type::Pair(Box::new(Type::Atom), Box::new(Type::Atom))
+                ),
+                "1:1".to_string()
+            ))
+        );
+        assert_eq!(
+            infer(&types(), "(cond ((cons 'A 'B) 'X) ('T 'Y))").map_err(|(kind, _)| kind),
+            Err(TypeErrorKind::Mismatch(
+                Type::Bool,
+                Type::Pair(Box::new(Type::Atom), Box::new(Type::Atom))
+            ))
+        );
+        assert_eq!(
+            infer(&types(), "(lambda (x) (apply x (,x)))").map_err(|(kind, _)| kind),
+            Err(TypeErrorKind::Infinite(Type::Fn(
+                vec![Type::Var(0)],
+                Box::new(Type::Var(1))
+            )))
+        );
+        assert_eq!(
+            infer(&types(), "(+ 1 (cons 'A 'B))").map_err(|(kind, _)| kind),
+            Err(TypeErrorKind::Mismatch(
+                Type::Integer,
+                Type::Pair(Box::new(Type::Atom), Box::new(Type::Atom))
+            ))
+        );
+        assert_eq!(
+            infer(&types(), "(explode 1)").map_err(|(kind, _)| kind),
+            Err(TypeErrorKind::Mismatch(Type::Symbol, Type::Integer))
+        );
+        assert_eq!(
+            infer(&types(), r#"(symbol->string "x")"#).map_err(|(kind, _)| kind),
+            Err(TypeErrorKind::Mismatch(Type::Symbol, Type::String))
+        );
+        assert_eq!(
+            infer(&types(), "x").map_err(|(kind, _)| kind),
+            Err(TypeErrorKind::UnboundVariable("x".to_string()))
+        );
+    }
+
+    #[test]
+    fn prelude() {
+        let mut env = Environment::new();
+        prelude::load(&mut env);
+        let types = TypeChecker::new(&env);
+
+        for (name, ty) in [
+            ("ff", "SExp -> SExp"),
+            ("equal", "(SExp, SExp) -> Bool"),
+            ("null", "a -> Bool"),
+            ("append", "(List<a>, List<a>) -> List<a>"),
+            ("cadr", "Pair<a, Pair<b, c>> -> b"),
+            (
+                "pair",
+                "(List<a>, List<b>) -> List<Pair<a, Pair<b, List<c>>>>",
+            ),
+        ] {
+            assert_eq!(infer(&types, name), Ok(ty.to_string()), "{}", name);
+        }
+        assert_eq!(
+            infer(&types, "(apply append (,'(A (B NIL)) ,'(C NIL)))"),
+            Ok("List<Atom>".to_string())
+        );
+        assert_eq!(
+            infer(&types, "(apply append (,'(A (B NIL)) ,(lambda () 'C)))")
+                .map_err(|(_, span)| span),
+            Err("1:1".to_string())
+        );
+    }
+}